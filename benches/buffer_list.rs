@@ -0,0 +1,65 @@
+//! Per-line cost of [BufferList] next to a bare [BufRead::read_until] loop over the same data.
+//! The read path takes the reader lock once per line, so the gap between the two is the lock,
+//! the shared counters and building the [String]. Run with `cargo bench --bench buffer_list`.
+
+use std::io::{BufRead, BufReader, Cursor};
+use std::time::{Duration, Instant};
+
+use iterman::BufferList;
+
+const LINES: usize = 1_000_000;
+const RUNS: u32 = 5;
+
+/// The fastest of `RUNS` runs of `f`, which returns how many lines it read.
+fn fastest(f: impl Fn() -> usize) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            assert_eq!(f(), LINES);
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let per_line = elapsed.as_nanos() as f64 / LINES as f64;
+    println!("{name:<24} {elapsed:>12.2?} {per_line:>8.1} ns/line");
+}
+
+fn main() {
+    let text = "test@example.com\n".repeat(LINES);
+
+    report(
+        "read_until",
+        fastest(|| {
+            let mut reader = BufReader::new(Cursor::new(text.as_bytes()));
+            let mut line = Vec::new();
+            let mut lines = 0;
+            while reader.read_until(b'\n', &mut line).unwrap() > 0 {
+                line.clear();
+                lines += 1;
+            }
+            lines
+        }),
+    );
+
+    report(
+        "BufferList::iter",
+        fastest(|| BufferList::new(BufReader::new(Cursor::new(text.as_bytes()))).count()),
+    );
+
+    report(
+        "BufferList::read_into",
+        fastest(|| {
+            let mut list = BufferList::new(BufReader::new(Cursor::new(text.as_bytes())));
+            let mut line = String::new();
+            let mut lines = 0;
+            while list.read_into(&mut line).is_some() {
+                line.clear();
+                lines += 1;
+            }
+            lines
+        }),
+    );
+}
@@ -0,0 +1,841 @@
+use crate::error::IterManError;
+use crate::list::ListLike;
+use std::collections::VecDeque;
+use std::iter::Peekable;
+use std::time::{Duration, Instant};
+
+/// An iterator over batches of up to `size` items from a [ListLike], created by
+/// [ListLike::batches].
+pub struct Batches<'a, L: ListLike> {
+    list: &'a mut L,
+    size: usize,
+}
+
+impl<'a, L: ListLike> Batches<'a, L> {
+    pub(crate) fn new(list: &'a mut L, size: usize) -> Self {
+        Self { list, size }
+    }
+}
+
+impl<L: ListLike> Iterator for Batches<'_, L> {
+    type Item = Vec<L::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut batch = Vec::with_capacity(self.size);
+        while batch.len() < self.size {
+            match self.list.iter() {
+                Some(item) => batch.push(item),
+                None => break,
+            }
+        }
+
+        if batch.is_empty() {
+            return None;
+        }
+        Some(batch)
+    }
+}
+
+/// An iterator over overlapping windows of `size` items that slides forward one item at a time,
+/// created by [crate::MemoryList::windows].
+pub struct Windows<'a, L: ListLike> {
+    list: &'a mut L,
+    size: usize,
+    window: VecDeque<L::Item>,
+}
+
+impl<'a, L: ListLike> Windows<'a, L> {
+    pub(crate) fn new(list: &'a mut L, size: usize) -> Self {
+        Self {
+            list,
+            size,
+            window: VecDeque::with_capacity(size),
+        }
+    }
+}
+
+impl<L: ListLike> Iterator for Windows<'_, L>
+where
+    L::Item: Clone,
+{
+    type Item = Vec<L::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.size == 0 {
+            return None;
+        }
+
+        if self.window.len() == self.size {
+            self.window.pop_front();
+        }
+
+        while self.window.len() < self.size {
+            self.window.push_back(self.list.iter()?);
+        }
+
+        Some(self.window.iter().cloned().collect())
+    }
+}
+
+/// A [ListLike] that only yields the items of another [ListLike] that match a predicate, created
+/// by [ListLike::with_filter]. Skipped items still move the underlying list, so its `line_index`
+/// and `bytes_offset` keep tracking the position in the source for saving and resuming.
+pub struct FilteredList<L: ListLike, F: FnMut(&L::Item) -> bool> {
+    list: L,
+    filter: F,
+}
+
+impl<L: ListLike, F: FnMut(&L::Item) -> bool> FilteredList<L, F> {
+    pub(crate) fn new(list: L, filter: F) -> Self {
+        Self { list, filter }
+    }
+
+    /// The underlying list, for reading its position.
+    pub fn get_ref(&self) -> &L {
+        &self.list
+    }
+
+    /// The underlying list, for seeking or restoring its position.
+    pub fn get_mut(&mut self) -> &mut L {
+        &mut self.list
+    }
+
+    /// Unwrap the underlying list, dropping the filter.
+    pub fn into_inner(self) -> L {
+        self.list
+    }
+}
+
+impl<L: ListLike, F: FnMut(&L::Item) -> bool> ListLike for FilteredList<L, F> {
+    type Item = L::Item;
+
+    fn iter(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.list.iter()?;
+            if (self.filter)(&item) {
+                return Some(item);
+            }
+        }
+    }
+
+    /// Items that don't match are consumed while looking for the next one that does.
+    fn peek(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.list.peek()?;
+            if (self.filter)(&item) {
+                return Some(item);
+            }
+            self.list.iter();
+        }
+    }
+}
+
+impl<L: ListLike, F: FnMut(&L::Item) -> bool> Iterator for FilteredList<L, F> {
+    type Item = L::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter()
+    }
+}
+
+/// An iterator over runs of consecutive items of a [ListLike] that share a key, created by
+/// [ListLike::group_by]. The item that ends a run is only peeked, so it's still in the list.
+pub struct GroupBy<L: ListLike, F> {
+    list: L,
+    key: F,
+}
+
+impl<L: ListLike, F> GroupBy<L, F> {
+    pub(crate) fn new(list: L, key: F) -> Self {
+        Self { list, key }
+    }
+
+    /// The underlying list, for reading its position.
+    pub fn get_ref(&self) -> &L {
+        &self.list
+    }
+
+    /// Unwrap the underlying list, dropping the key function.
+    pub fn into_inner(self) -> L {
+        self.list
+    }
+}
+
+impl<L: ListLike, K: PartialEq, F: FnMut(&L::Item) -> K> Iterator for GroupBy<L, F> {
+    type Item = (K, Vec<L::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.list.iter()?;
+        let key = (self.key)(&first);
+        let mut group = vec![first];
+        while let Some(item) = self.list.peek() {
+            if (self.key)(&item) != key {
+                break;
+            }
+            self.list.iter();
+            group.push(item);
+        }
+        Some((key, group))
+    }
+}
+
+/// A [ListLike] that reads one [ListLike] to the end and then another, created by
+/// [ListLike::chain_list]. Once the first list returns [None] it isn't read again, so a
+/// `round_robin` first list never hands over to the second.
+pub struct Chain<A: ListLike, B: ListLike<Item = A::Item>> {
+    first: A,
+    second: B,
+    first_done: bool,
+    line_index: usize,
+}
+
+impl<A: ListLike, B: ListLike<Item = A::Item>> Chain<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            first_done: false,
+            line_index: 0,
+        }
+    }
+
+    /// Number of items read from both lists together.
+    pub fn line_index(&self) -> usize {
+        self.line_index
+    }
+
+    /// The underlying lists, for reading their positions.
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+
+    /// Unwrap the underlying lists.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: ListLike, B: ListLike<Item = A::Item>> ListLike for Chain<A, B> {
+    type Item = A::Item;
+
+    fn iter(&mut self) -> Option<Self::Item> {
+        if !self.first_done {
+            match self.first.iter() {
+                Some(item) => {
+                    self.line_index += 1;
+                    return Some(item);
+                }
+                None => self.first_done = true,
+            }
+        }
+
+        let item = self.second.iter()?;
+        self.line_index += 1;
+        Some(item)
+    }
+
+    fn peek(&mut self) -> Option<Self::Item> {
+        if !self.first_done {
+            if let Some(item) = self.first.peek() {
+                return Some(item);
+            }
+        }
+        self.second.peek()
+    }
+}
+
+impl<A: ListLike, B: ListLike<Item = A::Item>> Iterator for Chain<A, B> {
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter()
+    }
+}
+
+/// A [ListLike] that takes items from two [ListLike]s in turn, starting with the first, created
+/// by [crate::interleave]. By default the other list carries on alone once one runs out; with
+/// [Interleave::with_stop_at_shortest] both stop instead. A list that returns [None] isn't read
+/// again.
+pub struct Interleave<A: ListLike, B: ListLike<Item = A::Item>> {
+    first: A,
+    second: B,
+    second_next: bool,
+    first_done: bool,
+    second_done: bool,
+    stop_at_shortest: bool,
+}
+
+impl<A: ListLike, B: ListLike<Item = A::Item>> Interleave<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            second_next: false,
+            first_done: false,
+            second_done: false,
+            stop_at_shortest: false,
+        }
+    }
+
+    /// Stop as soon as either list runs out when `stop_at_shortest` is true, rather than
+    /// draining the other.
+    pub fn with_stop_at_shortest(mut self, stop_at_shortest: bool) -> Self {
+        self.stop_at_shortest = stop_at_shortest;
+        self
+    }
+
+    /// The underlying lists, for reading their positions.
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+
+    /// Unwrap the underlying lists.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: ListLike, B: ListLike<Item = A::Item>> ListLike for Interleave<A, B> {
+    type Item = A::Item;
+
+    fn iter(&mut self) -> Option<Self::Item> {
+        // Try the list whose turn it is, then the other if that one has run out.
+        for _ in 0..2 {
+            let from_second = self.second_next;
+            self.second_next = !from_second;
+            let item = match from_second {
+                false if !self.first_done => self.first.iter(),
+                true if !self.second_done => self.second.iter(),
+                _ => continue,
+            };
+            if item.is_some() {
+                return item;
+            }
+
+            if from_second {
+                self.second_done = true;
+            } else {
+                self.first_done = true;
+            }
+            if self.stop_at_shortest {
+                self.first_done = true;
+                self.second_done = true;
+            }
+        }
+        None
+    }
+
+    fn peek(&mut self) -> Option<Self::Item> {
+        let turn = match self.second_next {
+            false if !self.first_done => self.first.peek(),
+            true if !self.second_done => self.second.peek(),
+            _ => None,
+        };
+        if turn.is_some() || self.stop_at_shortest {
+            return turn;
+        }
+
+        match self.second_next {
+            false if !self.second_done => self.second.peek(),
+            true if !self.first_done => self.first.peek(),
+            _ => None,
+        }
+    }
+}
+
+impl<A: ListLike, B: ListLike<Item = A::Item>> Iterator for Interleave<A, B> {
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter()
+    }
+}
+
+/// A [ListLike] that transforms every item of another [ListLike], created by
+/// [ListLike::map_items]. The underlying list does the reading, so its position and
+/// `round_robin` behavior carry over unchanged.
+pub struct MappedList<L: ListLike, F> {
+    list: L,
+    f: F,
+}
+
+impl<L: ListLike, F> MappedList<L, F> {
+    pub(crate) fn new(list: L, f: F) -> Self {
+        Self { list, f }
+    }
+
+    /// The underlying list, for reading its position.
+    pub fn get_ref(&self) -> &L {
+        &self.list
+    }
+
+    /// The underlying list, for seeking or restoring its position.
+    pub fn get_mut(&mut self) -> &mut L {
+        &mut self.list
+    }
+
+    /// Unwrap the underlying list, dropping the mapping.
+    pub fn into_inner(self) -> L {
+        self.list
+    }
+}
+
+impl<L: ListLike, U, F: FnMut(L::Item) -> U> ListLike for MappedList<L, F> {
+    type Item = U;
+
+    fn iter(&mut self) -> Option<Self::Item> {
+        self.list.iter().map(&mut self.f)
+    }
+
+    fn peek(&mut self) -> Option<Self::Item> {
+        self.list.peek().map(&mut self.f)
+    }
+
+    fn try_next(&mut self) -> Result<Option<Self::Item>, IterManError> {
+        Ok(self.list.try_next()?.map(&mut self.f))
+    }
+}
+
+impl<L: ListLike, U, F: FnMut(L::Item) -> U> Iterator for MappedList<L, F> {
+    type Item = U;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter()
+    }
+}
+
+/// A [ListLike] that yields the elements of every item of another [ListLike] in turn, created
+/// by [ListLike::flatten_items]. The underlying list is read one item at a time, so its position
+/// and `round_robin` behavior carry over, and `inner_index` tracks the position within the item
+/// being flattened.
+pub struct FlattenedList<L: ListLike>
+where
+    L::Item: IntoIterator,
+{
+    list: L,
+    current: Option<Peekable<<L::Item as IntoIterator>::IntoIter>>,
+    inner_index: usize,
+}
+
+impl<L: ListLike> FlattenedList<L>
+where
+    L::Item: IntoIterator,
+{
+    pub(crate) fn new(list: L) -> Self {
+        Self {
+            list,
+            current: None,
+            inner_index: 0,
+        }
+    }
+
+    /// How many elements of the current item have been yielded. The underlying list is already
+    /// past that item, so its position points at the next one.
+    pub fn inner_index(&self) -> usize {
+        self.inner_index
+    }
+
+    /// The underlying list, for reading its position.
+    pub fn get_ref(&self) -> &L {
+        &self.list
+    }
+
+    /// Unwrap the underlying list, dropping whatever is left of the current item.
+    pub fn into_inner(self) -> L {
+        self.list
+    }
+
+    /// Read items from the underlying list with `next_item` until one has an element left,
+    /// returning false once the list runs out.
+    fn fill(
+        &mut self,
+        mut next_item: impl FnMut(&mut L) -> Result<Option<L::Item>, IterManError>,
+    ) -> Result<bool, IterManError> {
+        loop {
+            if let Some(current) = &mut self.current {
+                if current.peek().is_some() {
+                    return Ok(true);
+                }
+            }
+
+            let Some(item) = next_item(&mut self.list)? else {
+                return Ok(false);
+            };
+            self.current = Some(item.into_iter().peekable());
+            self.inner_index = 0;
+        }
+    }
+
+    /// Take the next element once `fill` has found one.
+    fn take_element(&mut self) -> Option<<L::Item as IntoIterator>::Item> {
+        let element = self.current.as_mut()?.next()?;
+        self.inner_index += 1;
+        Some(element)
+    }
+}
+
+impl<L: ListLike> ListLike for FlattenedList<L>
+where
+    L::Item: IntoIterator,
+    <L::Item as IntoIterator>::Item: Clone,
+{
+    type Item = <L::Item as IntoIterator>::Item;
+
+    fn iter(&mut self) -> Option<Self::Item> {
+        if !self.fill(|list| Ok(list.iter())).unwrap_or(false) {
+            return None;
+        }
+        self.take_element()
+    }
+
+    /// Peeking past an empty or finished item reads the next one from the underlying list.
+    fn peek(&mut self) -> Option<Self::Item> {
+        if !self.fill(|list| Ok(list.iter())).unwrap_or(false) {
+            return None;
+        }
+        self.current.as_mut()?.peek().cloned()
+    }
+
+    fn try_next(&mut self) -> Result<Option<Self::Item>, IterManError> {
+        if !self.fill(L::try_next)? {
+            return Ok(None);
+        }
+        Ok(self.take_element())
+    }
+}
+
+impl<L: ListLike> Iterator for FlattenedList<L>
+where
+    L::Item: IntoIterator,
+    <L::Item as IntoIterator>::Item: Clone,
+{
+    type Item = <L::Item as IntoIterator>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter()
+    }
+}
+
+/// A [ListLike] that skips items equal to the one it yielded last, like `uniq`, created by
+/// [ListLike::dedup]. Only consecutive duplicates are collapsed.
+pub struct Dedup<L: ListLike> {
+    list: L,
+    last: Option<L::Item>,
+}
+
+impl<L: ListLike> Dedup<L> {
+    pub(crate) fn new(list: L) -> Self {
+        Self { list, last: None }
+    }
+
+    /// The underlying list, for reading its position.
+    pub fn get_ref(&self) -> &L {
+        &self.list
+    }
+
+    /// Unwrap the underlying list.
+    pub fn into_inner(self) -> L {
+        self.list
+    }
+}
+
+impl<L: ListLike> ListLike for Dedup<L>
+where
+    L::Item: PartialEq + Clone,
+{
+    type Item = L::Item;
+
+    fn iter(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.list.iter()?;
+            if self.last.as_ref() != Some(&item) {
+                self.last = Some(item.clone());
+                return Some(item);
+            }
+        }
+    }
+
+    /// Duplicates are consumed while looking for the next distinct item.
+    fn peek(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.list.peek()?;
+            if self.last.as_ref() != Some(&item) {
+                return Some(item);
+            }
+            self.list.iter();
+        }
+    }
+}
+
+impl<L: ListLike> Iterator for Dedup<L>
+where
+    L::Item: PartialEq + Clone,
+{
+    type Item = L::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter()
+    }
+}
+
+/// A [ListLike] that paces reads so consecutive items are at least `interval` apart, created by
+/// [ListLike::throttled]. Time the consumer spends between calls counts toward the interval, so
+/// it's a minimum gap rather than a fixed added delay.
+pub struct Throttled<L: ListLike> {
+    list: L,
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl<L: ListLike> Throttled<L> {
+    pub(crate) fn new(list: L, interval: Duration) -> Self {
+        Self {
+            list,
+            interval,
+            last: None,
+        }
+    }
+
+    /// The underlying list, for reading its position.
+    pub fn get_ref(&self) -> &L {
+        &self.list
+    }
+
+    /// Unwrap the underlying list.
+    pub fn into_inner(self) -> L {
+        self.list
+    }
+}
+
+impl<L: ListLike> ListLike for Throttled<L> {
+    type Item = L::Item;
+
+    fn iter(&mut self) -> Option<Self::Item> {
+        if let Some(last) = self.last {
+            let elapsed = last.elapsed();
+            if elapsed < self.interval {
+                std::thread::sleep(self.interval - elapsed);
+            }
+        }
+
+        let item = self.list.iter()?;
+        self.last = Some(Instant::now());
+        Some(item)
+    }
+
+    /// Peeking never waits.
+    fn peek(&mut self) -> Option<Self::Item> {
+        self.list.peek()
+    }
+}
+
+impl<L: ListLike> Iterator for Throttled<L> {
+    type Item = L::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list::{BufferList, ListLike, MemoryList};
+    use std::io::{BufReader, Cursor};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn it_should_batch_exact_multiples() {
+        let mut list = MemoryList::new(vec![1, 2, 3, 4]);
+        let batches = list.batches(2).collect::<Vec<Vec<i32>>>();
+        assert_eq!(batches, [vec![1, 2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn it_should_yield_a_short_final_batch() {
+        let mut list = BufferList::new(BufReader::new(Cursor::new("1\n2\n3\n")));
+        let batches = list.batches(2).collect::<Vec<Vec<String>>>();
+        assert_eq!(batches, [vec!["1", "2"], vec!["3"]]);
+    }
+
+    #[test]
+    fn it_should_yield_full_batches_with_round_robin() {
+        let mut list = MemoryList::new_round_robin(vec![1, 2, 3]);
+        let batches = list.batches(2).take(3).collect::<Vec<Vec<i32>>>();
+        assert_eq!(batches, [vec![1, 2], vec![3, 1], vec![2, 3]]);
+    }
+
+    #[test]
+    fn it_should_slide_windows() {
+        let mut list = MemoryList::new(vec![1, 2, 3, 4]);
+        let windows = list.windows(2).collect::<Vec<Vec<i32>>>();
+        assert_eq!(windows, [vec![1, 2], vec![2, 3], vec![3, 4]]);
+    }
+
+    #[test]
+    fn it_should_yield_no_windows_larger_than_the_list() {
+        let mut list = MemoryList::new(vec![1, 2, 3]);
+        assert_eq!(list.windows(4).count(), 0);
+    }
+
+    #[test]
+    fn it_should_yield_no_windows_for_an_empty_size() {
+        let mut list = MemoryList::new(vec![1, 2, 3]);
+        assert_eq!(list.windows(0).count(), 0);
+        assert_eq!(list.line_index(), 0);
+    }
+
+    #[test]
+    fn it_should_filter_while_tracking_the_source_position() {
+        let mut list = MemoryList::new(vec![1, 2, 3, 4, 5, 6, 7]).with_filter(|n| n % 2 == 0);
+        assert_eq!(list.next(), Some(2));
+        assert_eq!(list.get_ref().line_index(), 2);
+        assert_eq!(list.peek(), Some(4));
+        assert_eq!(list.get_ref().line_index(), 3);
+        assert_eq!(list.next(), Some(4));
+        assert_eq!(list.get_ref().line_index(), 4);
+
+        list.get_mut().seek(1).unwrap();
+        assert_eq!(list.collect::<Vec<i32>>(), [2, 4, 6]);
+    }
+
+    #[test]
+    fn it_should_filter_buffer_lists() {
+        let reader = BufReader::new(Cursor::new("keep\nskip\nkeep too\n"));
+        let mut list = BufferList::new(reader).with_filter(|line| line.starts_with("keep"));
+        assert_eq!(list.next(), Some("keep".to_string()));
+        assert_eq!(list.next(), Some("keep too".to_string()));
+        assert_eq!(list.get_ref().bytes_offset(), 19);
+        assert_eq!(list.next(), None);
+    }
+
+    #[test]
+    fn it_should_map_items_while_tracking_the_source_position() {
+        let mut list = MemoryList::new_round_robin(vec![1, 2, 3]).map_items(|n| format!("#{n}"));
+        assert_eq!(list.next(), Some("#1".to_string()));
+        assert_eq!(list.peek(), Some("#2".to_string()));
+        assert_eq!(list.get_ref().line_index(), 1);
+
+        list.get_mut().seek(2).unwrap();
+        assert_eq!(
+            list.by_ref().take(3).collect::<Vec<String>>(),
+            ["#3", "#1", "#2"]
+        );
+        assert_eq!(list.into_inner().line_index(), 2);
+    }
+
+    #[test]
+    fn it_should_group_runs_by_key() {
+        let list = MemoryList::new(vec!["a1", "a2", "b1", "a3"]);
+        let groups = list
+            .group_by(|item| item.chars().next())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            [
+                (Some('a'), vec!["a1", "a2"]),
+                (Some('b'), vec!["b1"]),
+                (Some('a'), vec!["a3"])
+            ]
+        );
+
+        let reader = BufReader::new(Cursor::new("10:01 a\n10:01 b\n10:02 c\n"));
+        let mut groups = BufferList::new(reader).group_by(|line| line[..5].to_string());
+        assert_eq!(
+            groups.next(),
+            Some((
+                "10:01".to_string(),
+                vec!["10:01 a".to_string(), "10:01 b".to_string()]
+            ))
+        );
+        assert_eq!(groups.get_ref().line_index(), 2);
+    }
+
+    #[test]
+    fn it_should_chain_different_lists() {
+        let first = MemoryList::new(vec!["a".to_string(), "b".to_string()]);
+        let second = BufferList::new(BufReader::new(Cursor::new("c\nd\n")));
+        let mut list = first.chain_list(second);
+        assert_eq!(list.next(), Some("a".to_string()));
+        assert_eq!(list.next(), Some("b".to_string()));
+        assert_eq!(list.peek(), Some("c".to_string()));
+        assert_eq!(list.line_index(), 2);
+        assert_eq!(list.by_ref().collect::<Vec<String>>(), ["c", "d"]);
+        assert_eq!(list.line_index(), 4);
+
+        let (first, second) = list.get_ref();
+        assert_eq!(first.line_index() + second.line_index(), 4);
+    }
+
+    #[test]
+    fn it_should_dedup_consecutive_items() {
+        let list = MemoryList::new(vec!["a", "a", "b", "a"]).dedup();
+        assert_eq!(list.collect::<Vec<&str>>(), ["a", "b", "a"]);
+
+        let reader = BufReader::new(Cursor::new("x\nx\nx\ny\n"));
+        let mut list = BufferList::new(reader).dedup();
+        assert_eq!(list.next(), Some("x".to_string()));
+        assert_eq!(list.peek(), Some("y".to_string()));
+        assert_eq!(list.get_ref().line_index(), 3);
+        assert_eq!(list.next(), Some("y".to_string()));
+        assert_eq!(list.next(), None);
+    }
+
+    #[test]
+    fn it_should_throttle_reads() {
+        let interval = Duration::from_millis(20);
+        let start = Instant::now();
+        let list = MemoryList::new(vec![1, 2, 3, 4]).throttled(interval);
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 2, 3, 4]);
+        assert!(start.elapsed() >= interval * 3);
+
+        // Time spent by the consumer counts: backdate the last read by a whole (long) interval
+        // instead of sleeping, so the next read shouldn't wait anywhere near it.
+        let interval = Duration::from_secs(10);
+        let mut list = MemoryList::new(vec![1, 2]).throttled(interval);
+        list.next();
+        list.last = list.last.and_then(|last| last.checked_sub(interval));
+        let start = Instant::now();
+        assert_eq!(list.next(), Some(2));
+        assert!(start.elapsed() < interval / 2);
+    }
+
+    #[test]
+    fn it_should_flatten_items() {
+        let list = MemoryList::new(vec![vec![1, 2], vec![3]]).flatten_items();
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 2, 3]);
+
+        let mut list =
+            MemoryList::new_round_robin(vec![vec![], vec![1, 2], vec![3]]).flatten_items();
+        assert_eq!(list.next(), Some(1));
+        assert_eq!((list.get_ref().line_index(), list.inner_index()), (2, 1));
+        assert_eq!(list.peek(), Some(2));
+        assert_eq!(list.next(), Some(2));
+        assert_eq!(list.peek(), Some(3));
+        assert_eq!((list.get_ref().line_index(), list.inner_index()), (3, 0));
+        assert_eq!(list.by_ref().take(3).collect::<Vec<i32>>(), [3, 1, 2]);
+    }
+
+    #[test]
+    fn it_should_interleave_two_lists() {
+        let a = MemoryList::new(vec!["1".to_string(), "3".to_string()]);
+        let b = BufferList::new(BufReader::new(Cursor::new("2\n4\n")));
+        let list = crate::interleave(a, b);
+        assert_eq!(list.collect::<Vec<String>>(), ["1", "2", "3", "4"]);
+
+        let mut list = crate::interleave(MemoryList::new(vec![1]), MemoryList::new(vec![2, 4, 6]));
+        assert_eq!(list.by_ref().take(2).collect::<Vec<i32>>(), [1, 2]);
+        assert_eq!(list.peek(), Some(4));
+        assert_eq!(list.collect::<Vec<i32>>(), [4, 6]);
+
+        let list = crate::interleave(MemoryList::new(vec![1, 3, 5]), MemoryList::new(vec![2]))
+            .with_stop_at_shortest(true);
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn it_should_yield_nothing_for_an_empty_batch_size() {
+        let mut list = MemoryList::new(vec![1, 2, 3]);
+        assert_eq!(list.batches(0).count(), 0);
+        assert_eq!(list.line_index(), 0);
+    }
+}
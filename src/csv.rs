@@ -0,0 +1,148 @@
+use crate::list::{BufferList, ListLike};
+use std::io::{Read, Seek};
+
+/// A [CsvList] splits the lines of a [BufferList] into rows of fields following RFC 4180, so
+/// quoted fields may hold commas, escaped `""` quotes and line breaks.
+/// # Examples
+/// ```no-run
+/// let reader = BufReader::new(Cursor::new("name,city\n\"Doe, Jane\",Paris\n"));
+/// let mut list = CsvList::new(BufferList::new(reader)).with_headers();
+/// assert_eq!(list.headers(), Some(&["name".to_string(), "city".to_string()][..]));
+/// assert_eq!(list.next(), Some(vec!["Doe, Jane".to_string(), "Paris".to_string()]));
+/// ```
+pub struct CsvList<T: Read + Seek> {
+    list: BufferList<T>,
+    headers: Option<Vec<String>>,
+}
+
+impl<T: Read + Seek> CsvList<T> {
+    /// Creates a new [CsvList] over `list`. Trimming is turned off on the list since whitespace
+    /// is significant in CSV fields.
+    pub fn new(list: BufferList<T>) -> Self {
+        Self {
+            list: list.with_trim(false),
+            headers: None,
+        }
+    }
+
+    /// Build a [CsvList] that reads the first row as the header, so it's returned by
+    /// [CsvList::headers] rather than by iteration.
+    pub fn with_headers(mut self) -> Self {
+        self.headers = self.read_row();
+        self
+    }
+
+    /// The header row, when the list was built with [CsvList::with_headers].
+    pub fn headers(&self) -> Option<&[String]> {
+        self.headers.as_deref()
+    }
+
+    /// The `line_index` of the underlying [BufferList]. A row with quoted line breaks spans more
+    /// than one line.
+    pub fn line_index(&self) -> usize {
+        self.list.line_index()
+    }
+
+    /// Reads lines until the quotes balance, then splits the record into fields.
+    fn read_row(&mut self) -> Option<Vec<String>> {
+        let mut record = strip_cr(self.list.iter()?);
+        while record.matches('"').count() % 2 == 1 {
+            match self.list.iter() {
+                Some(line) => {
+                    record.push('\n');
+                    record.push_str(&strip_cr(line));
+                }
+                None => break,
+            }
+        }
+
+        Some(split_fields(&record))
+    }
+}
+
+impl<T: Read + Seek> Iterator for CsvList<T> {
+    type Item = Vec<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_row()
+    }
+}
+
+/// Drops the `\r` left over from `\r\n` line endings.
+fn strip_cr(mut line: String) -> String {
+    if line.ends_with('\r') {
+        line.pop();
+    }
+    line
+}
+
+/// Splits one CSV record on commas outside quotes, unescaping quoted fields.
+fn split_fields(record: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = record.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    fields.push(field);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufReader, Cursor};
+
+    fn csv_list(text: &'static str) -> CsvList<Cursor<&'static str>> {
+        CsvList::new(BufferList::new(BufReader::new(Cursor::new(text))))
+    }
+
+    #[test]
+    fn csv_list_should_split_rows_into_fields() {
+        let list = csv_list("a,b,c\r\n1, 2 ,3\r\n");
+        assert_eq!(
+            list.collect::<Vec<Vec<String>>>(),
+            [vec!["a", "b", "c"], vec!["1", " 2 ", "3"]]
+        );
+    }
+
+    #[test]
+    fn csv_list_should_handle_quoted_fields() {
+        let mut list = csv_list("\"Doe, Jane\",\"say \"\"hi\"\"\"\n\"two\nlines\",x\n,\n");
+        assert_eq!(
+            list.next(),
+            Some(vec!["Doe, Jane".to_string(), "say \"hi\"".to_string()])
+        );
+        assert_eq!(
+            list.next(),
+            Some(vec!["two\nlines".to_string(), "x".to_string()])
+        );
+        assert_eq!(list.line_index(), 3);
+        assert_eq!(list.next(), Some(vec![String::new(), String::new()]));
+        assert_eq!(list.next(), None);
+    }
+
+    #[test]
+    fn csv_list_should_read_headers() {
+        let mut list = csv_list("name,age\nann,3\n").with_headers();
+        assert_eq!(
+            list.headers(),
+            Some(&["name".to_string(), "age".to_string()][..])
+        );
+        assert_eq!(list.next(), Some(vec!["ann".to_string(), "3".to_string()]));
+        assert_eq!(list.next(), None);
+
+        assert_eq!(csv_list("a\n").headers(), None);
+    }
+}
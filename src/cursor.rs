@@ -0,0 +1,28 @@
+/// A snapshot of a list's position that can be stored and later re-applied.
+/// # Examples
+/// ```no-run
+/// let mut list = MemoryList::new(vec![2, 3, 4]);
+/// list.next();
+/// let state = list.cursor();
+/// list.next();
+/// list.apply_cursor(state).unwrap();
+/// assert_eq!(list.next(), Some(3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CursorState {
+    pub line_index: usize,
+    /// Only set for buffer backed lists.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bytes_offset: Option<usize>,
+}
+
+/// Where a list is currently reading from, as returned by `position()`. It's the same type as
+/// [CursorState], so a position can be logged, stored or passed to `seek_position` directly.
+/// # Examples
+/// ```no-run
+/// let mut list = BufferList::new(BufReader::new(Cursor::new("hello\nworld")));
+/// list.next();
+/// assert_eq!(list.position(), Position { line_index: 1, bytes_offset: Some(6) });
+/// ```
+pub type Position = CursorState;
@@ -14,4 +14,61 @@ pub enum IterManError {
         bytes_offset: usize,
         max_len: usize,
     },
+    #[error("io error: {0}")]
+    Io(String),
+    #[error("missing list: {0}")]
+    MissingList(String),
+    #[error("invalid utf-8: {0}")]
+    Utf8(String),
+    #[error("invalid percentage: {0}, expected a value from 0.0 to 1.0")]
+    InvalidPercent(f64),
+    /// A thread panicked while reading from the shared reader, so its position can't be trusted.
+    #[error("poisoned lock: {0}")]
+    Poisoned(String),
+    #[error("the reader can only move forward")]
+    NotSeekable,
+    #[error("the list is empty")]
+    EmptyList,
+    #[error("invalid range: {start}..{end}, expected start to be at most end")]
+    InvalidRange { start: usize, end: usize },
+    /// The stream ended partway through a fixed-width record. The bytes that were read are kept.
+    #[error("partial record: expected {width} bytes, found {}", record.len())]
+    PartialRecord { width: usize, record: Vec<u8> },
+    /// A slice meant to hold one entry per list has a different length.
+    #[error("expected one entry per list: {expected}, found {found}")]
+    LengthMismatch { expected: usize, found: usize },
+    #[error("regex error: {0}")]
+    Regex(String),
+    /// A saved state no longer matches the list it was saved from.
+    #[error("the {list} list has changed length since it was saved: {saved} then, {found} now")]
+    ListLengthChanged {
+        list: String,
+        saved: usize,
+        found: usize,
+    },
+}
+
+impl From<std::io::Error> for IterManError {
+    fn from(e: std::io::Error) -> Self {
+        IterManError::Io(e.to_string())
+    }
+}
+
+impl<T> From<std::sync::PoisonError<T>> for IterManError {
+    fn from(e: std::sync::PoisonError<T>) -> Self {
+        IterManError::Poisoned(e.to_string())
+    }
+}
+
+#[cfg(feature = "regex")]
+impl From<fancy_regex::Error> for IterManError {
+    fn from(e: fancy_regex::Error) -> Self {
+        IterManError::Regex(e.to_string())
+    }
+}
+
+impl From<std::string::FromUtf8Error> for IterManError {
+    fn from(e: std::string::FromUtf8Error) -> Self {
+        IterManError::Utf8(e.to_string())
+    }
 }
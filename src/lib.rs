@@ -1,3 +1,38 @@
+mod adapter;
+#[cfg(feature = "csv")]
+mod csv;
+mod cursor;
 mod error;
 mod list;
 mod manager;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "shuffle")]
+mod shuffle;
+#[cfg(feature = "async")]
+mod stream;
+
+pub use adapter::{
+    Batches, Chain, Dedup, FilteredList, FlattenedList, GroupBy, Interleave, MappedList, Throttled,
+    Windows,
+};
+#[cfg(feature = "csv")]
+pub use csv::CsvList;
+pub use cursor::{CursorState, Position};
+pub use error::IterManError;
+#[cfg(feature = "graphemes")]
+pub use list::mem_list_from_graphemes;
+#[cfg(feature = "regex")]
+pub use list::mem_list_from_regex_split;
+pub use list::{
+    interleave, mem_list_from_chars, mem_list_from_chunks, mem_list_from_dir,
+    mem_list_from_dir_filtered, mem_list_from_dir_ordered, mem_list_from_dir_recursive,
+    mem_list_from_dir_with_paths, mem_list_from_line_chunks, mem_list_from_words, merge_sorted,
+    BufferArrayList, BufferList, BufferListBuilder, ForwardBufferList, InterleaveStrategy,
+    ListLike, MemoryArrayList, MemoryList, OrderBy, StdinList, TrimMode,
+};
+pub use manager::{Manager, ManagerBuilder};
+#[cfg(feature = "mmap")]
+pub use mmap::MmapList;
+#[cfg(feature = "async")]
+pub use stream::{BufferStream, StreamList};
@@ -1,15 +1,189 @@
+use crate::adapter::{
+    Batches, Chain, Dedup, FilteredList, FlattenedList, GroupBy, Interleave, MappedList, Throttled,
+    Windows,
+};
+use crate::cursor::{CursorState, Position};
 use crate::error::IterManError;
-use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+#[cfg(feature = "shuffle")]
+use crate::shuffle::{Picker, Shuffle};
+#[cfg(feature = "async")]
+use crate::stream::BufferStream;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Stdin};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, LockResult, Mutex, MutexGuard, PoisonError};
 
 pub trait ListLike {
     type Item;
 
     fn iter(&mut self) -> Option<Self::Item>;
+
+    /// Returns the item the next call to `iter` will yield without consuming it.
+    fn peek(&mut self) -> Option<Self::Item>;
+
+    /// Like `iter`, but separates the end of the list, `Ok(None)`, from a failure to read it.
+    /// In-memory lists can't fail, so by default this just wraps `iter`.
+    /// # Errors
+    /// Returns the [IterManError] that stopped the list from reading its next item.
+    fn try_next(&mut self) -> Result<Option<Self::Item>, IterManError> {
+        Ok(self.iter())
+    }
+
+    /// Read items while `f` holds, stopping before the first item it rejects so the next `iter`
+    /// returns that item. Unlike [Iterator::take_while] nothing is lost, so a list can be read in
+    /// predicate-delimited segments across several calls. A `round_robin` list where `f` never
+    /// fails reads forever.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![1, 2, 3, 10, 4]);
+    /// assert_eq!(list.take_while_persist(|n| *n < 5), [1, 2, 3]);
+    /// assert_eq!(list.next(), Some(10));
+    /// ```
+    fn take_while_persist<F: FnMut(&Self::Item) -> bool>(&mut self, mut f: F) -> Vec<Self::Item>
+    where
+        Self: Sized,
+    {
+        let mut taken = vec![];
+        while let Some(item) = self.peek() {
+            if !f(&item) {
+                break;
+            }
+            self.iter();
+            taken.push(item);
+        }
+        taken
+    }
+
+    /// Returns an iterator of [Vec]s holding up to `size` items each. The final batch is short
+    /// when the list runs out, and a `size` of 0 yields nothing.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![1, 2, 3]);
+    /// assert_eq!(list.batches(2).collect::<Vec<_>>(), [vec![1, 2], vec![3]]);
+    /// ```
+    fn batches(&mut self, size: usize) -> Batches<'_, Self>
+    where
+        Self: Sized,
+    {
+        Batches::new(self, size)
+    }
+
+    /// Wraps the list so it only yields items matching `filter`. Unlike [Iterator::filter], the
+    /// wrapped list stays reachable, so its position can still be saved and restored.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![1, 2, 3, 4]).with_filter(|n| n % 2 == 0);
+    /// assert_eq!(list.next(), Some(2));
+    /// assert_eq!(list.get_ref().line_index(), 2);
+    /// ```
+    fn with_filter<F: FnMut(&Self::Item) -> bool>(self, filter: F) -> FilteredList<Self, F>
+    where
+        Self: Sized,
+    {
+        FilteredList::new(self, filter)
+    }
+
+    /// Wraps the list so consecutive equal items are only yielded once, like `uniq`.
+    /// # Examples
+    /// ```no-run
+    /// let list = MemoryList::new(vec!["a", "a", "b", "a"]).dedup();
+    /// assert_eq!(list.collect::<Vec<&str>>(), ["a", "b", "a"]);
+    /// ```
+    fn dedup(self) -> Dedup<Self>
+    where
+        Self: Sized,
+        Self::Item: PartialEq + Clone,
+    {
+        Dedup::new(self)
+    }
+
+    /// Wraps the list so consecutive reads are at least `interval` apart, sleeping only for
+    /// whatever part of the interval the caller hasn't already spent.
+    /// # Examples
+    /// ```no-run
+    /// let list = MemoryList::new(emails).throttled(Duration::from_millis(500));
+    /// for email in list {
+    ///     send(email);
+    /// }
+    /// ```
+    fn throttled(self, interval: std::time::Duration) -> Throttled<Self>
+    where
+        Self: Sized,
+    {
+        Throttled::new(self, interval)
+    }
+
+    /// Returns an iterator of the runs of consecutive items that share a key, as the key and the
+    /// items of the run. An item with a new key ends the run and starts the next one, so equal
+    /// keys that aren't next to each other end up in separate groups.
+    /// # Examples
+    /// ```no-run
+    /// let list = BufferList::from_path("app.log").unwrap();
+    /// for (timestamp, lines) in list.group_by(|line| line[..19].to_string()) {
+    ///     println!("{timestamp}: {} lines", lines.len());
+    /// }
+    /// ```
+    fn group_by<K: PartialEq, F: FnMut(&Self::Item) -> K>(self, f: F) -> GroupBy<Self, F>
+    where
+        Self: Sized,
+    {
+        GroupBy::new(self, f)
+    }
+
+    /// Returns a [ListLike] that reads this list to the end and then carries on with `other`,
+    /// like [Iterator::chain], for sources of different types that yield the same items.
+    /// # Examples
+    /// ```no-run
+    /// let seeds = MemoryList::new(vec!["a@b.com".to_string()]);
+    /// let clients = BufferList::from_path("clients.txt").unwrap();
+    /// let mut list = seeds.chain_list(clients);
+    /// assert_eq!(list.next(), Some("a@b.com".to_string()));
+    /// ```
+    fn chain_list<B: ListLike<Item = Self::Item>>(self, other: B) -> Chain<Self, B>
+    where
+        Self: Sized,
+    {
+        Chain::new(self, other)
+    }
+
+    /// Wraps the list so every item is transformed by `f`. Unlike [Iterator::map] the result is
+    /// still a [ListLike], and the underlying list stays reachable for its position.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::new(reader).map_items(|line| line.to_uppercase());
+    /// assert_eq!(list.next(), Some("HELLO".to_string()));
+    /// assert_eq!(list.get_ref().line_index(), 1);
+    /// ```
+    fn map_items<U, F: FnMut(Self::Item) -> U>(self, f: F) -> MappedList<Self, F>
+    where
+        Self: Sized,
+    {
+        MappedList::new(self, f)
+    }
+
+    /// Yield the elements of each item in turn, such as every number of a list of [Vec]s. The
+    /// list is still read one item at a time, so its position and `round_robin` behavior carry
+    /// over. A `round_robin` list whose items are all empty never yields or returns.
+    /// # Examples
+    /// ```no-run
+    /// let list = MemoryList::new(vec![vec![1, 2], vec![3]]).flatten_items();
+    /// assert_eq!(list.collect::<Vec<i32>>(), [1, 2, 3]);
+    /// ```
+    fn flatten_items(self) -> FlattenedList<Self>
+    where
+        Self: Sized,
+        Self::Item: IntoIterator,
+    {
+        FlattenedList::new(self)
+    }
 }
 
-/// A [MemoryList] is a [ListLike] that reads from a [Vec].
+/// A [MemoryList] is a [ListLike] that reads from a [Vec]. Clones share the same [Vec] and cursor,
+/// so handles on different threads split the items between them like a work queue.
 /// # Examples
 /// ```no-run
 /// let list = MemoryList::new(vec![2, 3, 4]);
@@ -18,7 +192,47 @@ pub trait ListLike {
 pub struct MemoryList<T: Clone> {
     vec: Arc<Mutex<Vec<T>>>,
     round_robin: bool,
-    line_index: AtomicUsize,
+    line_index: Arc<AtomicUsize>,
+    /// Number of items taken from the back by `next_back`.
+    tail_taken: Arc<AtomicUsize>,
+    /// How many full passes a `round_robin` list makes before it stops. [None] repeats forever.
+    max_cycles: Option<usize>,
+    /// Number of times a `round_robin` list has wrapped back to the start.
+    cycles: Arc<AtomicUsize>,
+    #[cfg(feature = "shuffle")]
+    shuffle: Option<Arc<Mutex<Shuffle>>>,
+    /// Every position transition, once turned on by `with_seek_log`.
+    seek_log: Option<Vec<(Position, Position)>>,
+    /// How many positions each `next` moves forward, set by `stride`.
+    stride: usize,
+}
+
+impl<T: Clone> Clone for MemoryList<T> {
+    fn clone(&self) -> Self {
+        Self {
+            vec: Arc::clone(&self.vec),
+            round_robin: self.round_robin,
+            line_index: Arc::clone(&self.line_index),
+            tail_taken: Arc::clone(&self.tail_taken),
+            max_cycles: self.max_cycles,
+            cycles: Arc::clone(&self.cycles),
+            #[cfg(feature = "shuffle")]
+            shuffle: self.shuffle.clone(),
+            seek_log: self.seek_log.clone(),
+            stride: self.stride,
+        }
+    }
+}
+
+/// Shows the cursor and the number of items, but not the items themselves.
+impl<T: Clone> fmt::Debug for MemoryList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryList")
+            .field("round_robin", &self.round_robin)
+            .field("line_index", &self.line_index())
+            .field("len", &self.total_len())
+            .finish_non_exhaustive()
+    }
 }
 
 impl<T: Clone> MemoryList<T> {
@@ -26,7 +240,14 @@ impl<T: Clone> MemoryList<T> {
         Self {
             vec: Arc::new(Mutex::new(vec)),
             round_robin: false,
-            line_index: AtomicUsize::new(0),
+            line_index: Arc::new(AtomicUsize::new(0)),
+            tail_taken: Arc::new(AtomicUsize::new(0)),
+            max_cycles: None,
+            cycles: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "shuffle")]
+            shuffle: None,
+            seek_log: None,
+            stride: 1,
         }
     }
 
@@ -38,586 +259,6260 @@ impl<T: Clone> MemoryList<T> {
         }
     }
 
-    /// Build a [MemoryList]] and set the initial `line_index` pointer.
-    /// # Examples
-    /// ```no-run
-    /// let mut list = MemoryList::new_round_robin(vec![2, 3, 4]).with_seek_to(2);
-    /// ```
-    pub fn with_seek_to(mut self, line_index: usize) -> Self {
-        self.seek(line_index).unwrap_or_default();
-        self
-    }
-
-    /// Seek
-    /// Should this be public?
-    /// TODO: Revisit when persistence is added
-    pub fn seek(&mut self, line_index: usize) -> Result<usize, IterManError> {
-        if line_index < self.vec.lock().unwrap().len() {
-            self.line_index.store(line_index, Ordering::Relaxed);
-            return Ok(line_index);
+    /// Like [MemoryList::new], but treats an empty `vec` as a mistake rather than a list that
+    /// yields nothing.
+    /// # Errors
+    /// Returns [IterManError::EmptyList] if `vec` is empty.
+    pub fn try_new(vec: Vec<T>) -> Result<Self, IterManError> {
+        if vec.is_empty() {
+            return Err(IterManError::EmptyList);
         }
-
-        Err(IterManError::MemoryOutOfBounds {
-            line_index,
-            max_len: self.vec.lock().unwrap().len(),
-        })
+        Ok(Self::new(vec))
     }
 
-    pub fn line_index(&self) -> usize {
-        self.line_index.load(Ordering::Relaxed)
+    /// Like [MemoryList::new_round_robin], but treats an empty `vec` as a mistake.
+    /// # Errors
+    /// Returns [IterManError::EmptyList] if `vec` is empty.
+    pub fn try_new_round_robin(vec: Vec<T>) -> Result<Self, IterManError> {
+        Ok(Self {
+            round_robin: true,
+            ..Self::try_new(vec)?
+        })
     }
-}
-
-impl<T: Clone> ListLike for MemoryList<T> {
-    type Item = T;
-
-    fn iter(&mut self) -> Option<Self::Item> {
-        if self.round_robin && self.line_index() >= self.vec.lock().unwrap().len() {
-            self.line_index.store(0, Ordering::Relaxed);
-        }
 
-        if self.line_index() < self.vec.lock().unwrap().len() {
-            let val = self.vec.lock().unwrap()[self.line_index()].clone();
-            self.line_index.fetch_add(1, Ordering::SeqCst);
-            Some(val)
-        } else {
-            None
+    /// Creates a new [MemoryList] with `round_robin` turned on that stops after `times` full
+    /// passes. A `times` of 0 yields nothing and 1 behaves like [MemoryList::new].
+    /// # Examples
+    /// ```no-run
+    /// let list = MemoryList::new_round_robin_times(vec![1, 2, 3], 2);
+    /// assert_eq!(list.collect::<Vec<i32>>(), [1, 2, 3, 1, 2, 3]);
+    /// ```
+    pub fn new_round_robin_times(vec: Vec<T>, times: usize) -> Self {
+        Self {
+            max_cycles: Some(times),
+            ..Self::new_round_robin(vec)
         }
     }
-}
-
-impl<T: Clone> Iterator for MemoryList<T>
-where
-    T: Clone,
-{
-    type Item = T;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        MemoryList::iter(self)
-    }
-}
-
-/// A [BufferList] is a [ListLike] that reads from a [BufReader].
-/// # Examples
-/// ```no-run
-/// let reader = BufReader::new(Cursor::new("hello\nworld"));
-/// let list = BufferList::new(reader);
-/// assert_eq!(list.collect::<Vec<String>>(), ["hello", "world"]);
-/// ```
-pub struct BufferList<T: Read + Seek> {
-    buf_reader: Arc<Mutex<BufReader<T>>>,
-    round_robin: bool,
-    line_index: AtomicUsize,
-    bytes_offset: AtomicUsize,
-}
 
-impl<T: Read + Seek> BufferList<T> {
-    pub fn new(buf_reader: BufReader<T>) -> Self {
+    /// Creates a new [MemoryList] that yields the items in a random order drawn from `seed`. The
+    /// underlying [Vec] isn't reordered, and the same seed always gives the same order.
+    /// # Examples
+    /// ```no-run
+    /// let list = MemoryList::new_shuffled(vec![1, 2, 3, 4], 42);
+    /// ```
+    #[cfg(feature = "shuffle")]
+    pub fn new_shuffled(vec: Vec<T>, seed: u64) -> Self {
         Self {
-            buf_reader: Arc::new(Mutex::new(buf_reader)),
-            round_robin: false,
-            line_index: AtomicUsize::new(0),
-            bytes_offset: AtomicUsize::new(0),
+            shuffle: Some(Arc::new(Mutex::new(Shuffle::new(vec.len(), seed)))),
+            ..Self::new(vec)
         }
     }
 
-    /// Creates a new [BufferList] with `round_robin` turned on.
-    pub fn new_round_robin(buf_reader: BufReader<T>) -> Self {
+    /// Creates a new shuffled [MemoryList] with `round_robin` turned on. A new order is drawn
+    /// every time the list wraps around.
+    #[cfg(feature = "shuffle")]
+    pub fn new_shuffled_round_robin(vec: Vec<T>, seed: u64) -> Self {
         Self {
             round_robin: true,
-            ..Self::new(buf_reader)
+            ..Self::new_shuffled(vec, seed)
         }
     }
 
-    /// Build a [BufferList]] and set the initial `line_index` and `bytes_offset` pointers.
+    /// Build a [MemoryList]] and set the initial `line_index` pointer.
     /// # Examples
     /// ```no-run
-    /// let reader = BufReader::new(Cursor::new("hello\nworld"));
-    /// let list = StreamList::new(reader).with_seek_to(1, 6);
+    /// let mut list = MemoryList::new_round_robin(vec![2, 3, 4]).with_seek_to(2);
     /// ```
-    pub fn with_seek_to(mut self, line_index: usize, bytes_offset: usize) -> Self {
-        self.seek(line_index, bytes_offset).unwrap_or_default();
+    pub fn with_seek_to(mut self, line_index: usize) -> Self {
+        self.seek(line_index).unwrap_or_default();
         self
     }
 
-    /// Used internally to manage the line index and byte offset
-    fn incr(&mut self, bytes_read: &usize) {
-        self.line_index.fetch_add(1, Ordering::SeqCst);
-        self.bytes_offset.fetch_add(*bytes_read, Ordering::SeqCst);
+    /// Build a [MemoryList] that records the position before and after every `seek`, `reset` and
+    /// `next`, for working out where a cursor jumped. Read the log with
+    /// [MemoryList::seek_history]. Lists without it skip the bookkeeping entirely.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![1, 2, 3]).with_seek_log();
+    /// list.seek(2).unwrap();
+    /// list.next();
+    /// println!("{:?}", list.seek_history());
+    /// ```
+    pub fn with_seek_log(mut self) -> Self {
+        self.seek_log = Some(Vec::new());
+        self
     }
 
-    /// Reset the line index and byte offset
-    pub fn reset(&mut self) {
-        self.line_index.store(0, Ordering::Relaxed);
-        self.bytes_offset.store(0, Ordering::Relaxed);
+    /// The `(from, to)` positions recorded since [MemoryList::with_seek_log], oldest first. Empty
+    /// when the log is off.
+    pub fn seek_history(&self) -> &[(Position, Position)] {
+        self.seek_log.as_deref().unwrap_or_default()
     }
 
-    pub fn seek(&mut self, line_index: usize, bytes_offset: usize) -> Result<usize, IterManError> {
-        // https://doc.rust-lang.org/stable/std/io/trait.Seek.html#method.stream_len
-        let stream_len = match self.buf_reader.lock().unwrap().seek(SeekFrom::End(0)).ok() {
-            None => {
-                return Err(IterManError::StreamOutOfBounds {
-                    line_index,
-                    bytes_offset,
-                    max_len: 0,
-                })
-            }
-            Some(len) => len,
+    /// Run `f` and record the position it moved the list between. The log is taken out while `f`
+    /// runs, so any moves `f` makes along the way aren't recorded separately.
+    fn logged<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let Some(mut seek_log) = self.seek_log.take() else {
+            return f(self);
         };
+        let from = Self::position(self);
+        let result = f(self);
+        seek_log.push((from, Self::position(self)));
+        self.seek_log = Some(seek_log);
+        result
+    }
 
-        if stream_len < bytes_offset as u64 {
-            return Err(IterManError::StreamOutOfBounds {
-                line_index,
-                bytes_offset,
-                max_len: stream_len as usize,
-            });
+    /// Move the `line_index` pointer. Seeking to the length of the list positions it at the end.
+    pub fn seek(&mut self, line_index: usize) -> Result<usize, IterManError> {
+        if self.seek_log.is_some() {
+            return self.logged(|list| list.seek(line_index));
         }
 
-        if self
-            .buf_reader
-            .lock()
-            .unwrap()
-            .seek(SeekFrom::Start(bytes_offset as u64))
-            .ok()
-            .is_some()
-        {
+        if line_index <= lock(&self.vec).len() {
             self.line_index.store(line_index, Ordering::Relaxed);
-            self.bytes_offset.store(bytes_offset, Ordering::Relaxed);
-            return Ok(self.bytes_offset());
+            return Ok(line_index);
         }
 
-        Err(IterManError::StreamOutOfBounds {
+        Err(IterManError::MemoryOutOfBounds {
             line_index,
-            bytes_offset,
-            max_len: stream_len as usize,
+            max_len: lock(&self.vec).len(),
         })
     }
 
-    pub fn line_index(&self) -> usize {
-        self.line_index.load(Ordering::Relaxed)
+    /// Total number of items in the list, regardless of the current `line_index`. Unlike
+    /// [Iterator::count] this doesn't consume anything.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![2, 3, 4]);
+    /// list.next();
+    /// assert_eq!(list.total_len(), 3);
+    /// ```
+    pub fn total_len(&self) -> usize {
+        lock(&self.vec).len()
     }
 
-    pub fn bytes_offset(&self) -> usize {
-        self.bytes_offset.load(Ordering::Relaxed)
+    /// A clone of the item at `index`, or [None] past the end. The index is into the underlying
+    /// [Vec], ignoring the cursor, `stride` and any shuffle, and `line_index` isn't touched.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![2, 3, 4]);
+    /// list.next();
+    /// assert_eq!(list.get(0), Some(2));
+    /// assert_eq!(list.get(3), None);
+    /// assert_eq!(list.next(), Some(3));
+    /// ```
+    pub fn get(&self, index: usize) -> Option<T> {
+        lock(&self.vec).get(index).cloned()
     }
-}
 
-impl<T: Read + Seek> ListLike for BufferList<T> {
-    type Item = String;
+    /// Append an item to the end of the list. Clones see it too, so a consumer that already got
+    /// [None] from a list without `round_robin` will get the new item on its next call. A [None]
+    /// is therefore only final once nothing else can push to the list.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![1]);
+    /// assert_eq!(list.next(), Some(1));
+    /// assert_eq!(list.next(), None);
+    /// list.push(2);
+    /// assert_eq!(list.next(), Some(2));
+    /// ```
+    pub fn push(&mut self, item: T) {
+        self.extend([item]);
+    }
 
-    fn iter(&mut self) -> Option<Self::Item> {
-        let mut string = String::new();
-
-        // Scope of immutable borrow is limited here.
-        match {
-            let mut buf = self.buf_reader.lock().ok()?;
-            buf.read_line(&mut string).ok()?
-        } {
-            0 => {
-                if !self.round_robin {
-                    return None;
-                }
+    /// Append every item in `items` to the end of the list, as with [MemoryList::push].
+    pub fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        let mut vec = lock(&self.vec);
+        vec.extend(items);
 
-                {
-                    let mut buf = self.buf_reader.lock().ok()?;
-                    buf.seek(SeekFrom::Start(0)).ok()?;
-                }
+        #[cfg(feature = "shuffle")]
+        if let Some(shuffle) = &self.shuffle {
+            lock(shuffle).grow(vec.len());
+        }
+    }
 
-                self.reset();
-
-                return match {
-                    let mut buf = self.buf_reader.lock().ok()?;
-                    buf.read_line(&mut string)
-                } {
-                    Ok(bytes_read) => match bytes_read {
-                        0 => None, // Needed to stop empty buffer from returning ""
-                        _ => {
-                            self.incr(&bytes_read);
-                            Some(string.trim().to_string())
-                        }
-                    },
-                    Err(_) => None,
-                };
+    /// Iterate over every item from back to front with a fresh cursor, leaving this list's cursor
+    /// and the order of the [Vec] alone for other handles. A `round_robin` list starts again from
+    /// the last item after the first, for up to `max_cycles` passes.
+    /// # Examples
+    /// ```no-run
+    /// let list = MemoryList::new(vec![1, 2, 3]);
+    /// assert_eq!(list.iter_rev().collect::<Vec<i32>>(), [3, 2, 1]);
+    /// assert_eq!(list.line_index(), 0);
+    /// ```
+    pub fn iter_rev(&self) -> impl Iterator<Item = T> + '_ {
+        let mut cycle = 0;
+        let mut taken = 0;
+        std::iter::from_fn(move || {
+            let vec = lock(&self.vec);
+            if taken >= vec.len() && self.round_robin && !vec.is_empty() {
+                cycle += 1;
+                taken = 0;
             }
-            bytes_read => {
-                self.incr(&bytes_read);
-                Some(string.trim().to_string())
+            if !self.cycle_allowed(cycle) || taken >= vec.len() {
+                return None;
             }
-        }
-    }
-}
 
-impl<T: Read + Seek> Iterator for BufferList<T>
-where
-    T: Read + Seek,
-{
-    type Item = String;
+            taken += 1;
+            vec.get(self.source_index(vec.len() - taken)).cloned()
+        })
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        BufferList::iter(self)
+    /// Sort the items in place by the key `f` returns, keeping equal items in their original
+    /// order, then [MemoryList::reset] the cursor to the first item. This changes shared state:
+    /// clones share the [Vec] and cursor, so they see the new order from the start too, while
+    /// lists split off by [MemoryList::tee] keep their own position in the reordered items.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec!["ccc", "a", "bb"]);
+    /// list.sort_by_key(|word| word.len());
+    /// assert_eq!(list.collect::<Vec<&str>>(), ["a", "bb", "ccc"]);
+    /// ```
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, f: F) {
+        lock(&self.vec).sort_by_key(f);
+        self.reset();
     }
-}
 
-/// A [MemoryArrayList] is a [ListLike] that reads from a [Vec] of [Vec]s.
-pub struct MemoryArrayList<T: Clone> {
-    lists: Arc<Mutex<Vec<Vec<T>>>>,
-    round_robin: bool,
-    cur_list_index: AtomicUsize,
-    line_indexes: Arc<Mutex<Vec<usize>>>,
-    finished_count: AtomicUsize,
-}
+    /// Sort the items in place into ascending order, as with [MemoryList::sort_by_key].
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        lock(&self.vec).sort();
+        self.reset();
+    }
 
-impl<T: Clone> MemoryArrayList<T> {
-    /// Creates a new [MemoryArrayList] with `round_robin` turned off.
+    /// Split off two lists that read the same [Vec] without copying it, each with its own cursor
+    /// starting from the current position. Unlike clones, reading from one doesn't move the
+    /// other. There's no [BufferList] equivalent since a reader only has one position; clone the
+    /// reader itself and build a second [BufferList] instead.
     /// # Examples
     /// ```no-run
-    /// let mem_arr = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
-    /// let list = MemoryArrayList::new(mem_arr);
-    /// assert_eq!(
-    ///   list.collect::<Vec<i32>>(),
-    ///  [1, 4, 7, 2, 5, 8, 3, 6, 9]
-    /// );
+    /// let (mut a, mut b) = MemoryList::new(vec![1, 2, 3]).tee();
+    /// assert_eq!(a.next(), Some(1));
+    /// assert_eq!(a.next(), Some(2));
+    /// assert_eq!(b.next(), Some(1));
     /// ```
-    pub fn new(mem_arr: Vec<Vec<T>>) -> Self {
+    pub fn tee(&self) -> (Self, Self) {
+        (self.fork(), self.fork())
+    }
+
+    /// A list over the same [Vec] with a copy of the cursor rather than a shared one.
+    fn fork(&self) -> Self {
         Self {
-            lists: Arc::new(Mutex::new(mem_arr.clone())),
-            round_robin: false,
-            cur_list_index: AtomicUsize::new(0),
-            line_indexes: Arc::new(Mutex::new(vec![0; mem_arr.len()])),
-            finished_count: AtomicUsize::new(0),
+            vec: Arc::clone(&self.vec),
+            round_robin: self.round_robin,
+            line_index: Arc::new(AtomicUsize::new(self.line_index())),
+            tail_taken: Arc::new(AtomicUsize::new(self.tail_taken.load(Ordering::Relaxed))),
+            max_cycles: self.max_cycles,
+            cycles: Arc::new(AtomicUsize::new(self.cycles.load(Ordering::Relaxed))),
+            #[cfg(feature = "shuffle")]
+            shuffle: self
+                .shuffle
+                .as_ref()
+                .map(|shuffle| Arc::new(Mutex::new(lock(shuffle).clone()))),
+            seek_log: self.seek_log.clone(),
+            stride: self.stride,
         }
     }
 
-    /// Creates a new [MemoryArrayList] with `round_robin` turned on.
+    /// Consume the list and return the underlying [Vec], in its original order.
     /// # Examples
     /// ```no-run
-    /// let mem_arr = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
-    /// let list = MemoryArrayList::new_round_robin(mem_arr);
-    /// assert_eq!(
-    ///    list.take(15).collect::<Vec<i32>>(),
-    ///   [1, 4, 7, 2, 5, 8, 3, 6, 9]
-    /// );
+    /// let list = MemoryList::new(vec![2, 3, 4]);
+    /// assert_eq!(list.into_inner(), [2, 3, 4]);
     /// ```
-    pub fn new_round_robin(mem_arr: Vec<Vec<T>>) -> Self {
-        Self {
-            round_robin: true,
-            ..Self::new(mem_arr)
-        }
+    /// # Panics
+    /// Panics if a clone or [MemoryList::tee] of this list is still alive, since they share the
+    /// same [Vec].
+    pub fn into_inner(self) -> Vec<T> {
+        Arc::try_unwrap(self.vec)
+            .unwrap_or_else(|_| panic!("MemoryList::into_inner called while a clone is alive"))
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
     }
-}
 
-impl<T: Clone> Iterator for MemoryArrayList<T>
-where
-    T: Clone,
+    /// Skip up to `n` items without cloning them, returning how many were skipped. This follows
+    /// the same rules as `iter`, so a `round_robin` list wraps around and only stops early once
+    /// it runs out of passes. The count is in raw positions and ignores `stride`, so `n` moves
+    /// `line_index` by `n` rather than skipping `n` strided items.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![2, 3, 4]);
+    /// assert_eq!(list.advance(2), 2);
+    /// assert_eq!(list.next(), Some(4));
+    /// assert_eq!(list.advance(5), 0);
+    /// ```
+    pub fn advance(&mut self, n: usize) -> usize {
+        let vec = lock(&self.vec);
+        let mut skipped = 0;
+        while skipped < n {
+            let cycles = self.cycles.load(Ordering::Relaxed);
+            if !self.cycle_allowed(cycles) {
+                break;
+            }
+
+            let mut line_index = self.line_index();
+            if self.round_robin && line_index >= vec.len() {
+                if vec.is_empty() || !self.cycle_allowed(cycles + 1) {
+                    break;
+                }
+                self.cycles.store(cycles + 1, Ordering::SeqCst);
+                line_index = 0;
+            }
+
+            let end_index = self.end_index(vec.len());
+            if line_index >= end_index {
+                break;
+            }
+
+            let step = (n - skipped).min(end_index - line_index);
+            self.line_index.store(line_index + step, Ordering::SeqCst);
+            skipped += step;
+
+            #[cfg(feature = "shuffle")]
+            if self.round_robin && line_index + step >= vec.len() {
+                if let Some(shuffle) = &self.shuffle {
+                    lock(shuffle).reshuffle();
+                }
+            }
+        }
+
+        skipped
+    }
+
+    /// Move the `line_index` pointer forward, or backward for a negative `delta`, returning the new
+    /// `line_index`. Moving back past the start clamps to 0.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![2, 3, 4]).with_seek_to(2);
+    /// assert_eq!(list.seek_by(-1).unwrap(), 1);
+    /// assert_eq!(list.seek_by(-10).unwrap(), 0);
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::MemoryOutOfBounds] when moving past the end of the list, leaving the
+    /// position unchanged.
+    pub fn seek_by(&mut self, delta: isize) -> Result<usize, IterManError> {
+        self.seek(self.line_index().saturating_add_signed(delta))
+    }
+
+    pub fn line_index(&self) -> usize {
+        self.line_index.load(Ordering::Relaxed)
+    }
+
+    /// Reset the line index, including any items taken from the back by `next_back`.
+    pub fn reset(&mut self) {
+        if self.seek_log.is_some() {
+            return self.logged(Self::reset);
+        }
+
+        self.line_index.store(0, Ordering::Relaxed);
+        self.tail_taken.store(0, Ordering::Relaxed);
+        self.cycles.store(0, Ordering::Relaxed);
+    }
+
+    /// Turn `round_robin` on or off without rebuilding the list. Turning it on for a list that
+    /// has been read to the end makes the next read start again from the first item. Clones keep
+    /// the setting they were created with.
+    pub fn set_round_robin(&mut self, round_robin: bool) {
+        self.round_robin = round_robin;
+    }
+
+    /// How many items `next` yields from `positions` positions with the current `stride`.
+    fn strided(&self, positions: usize) -> usize {
+        (positions + self.stride - 1) / self.stride
+    }
+
+    /// Make every `next` move forward `n` positions, yielding one item and skipping the `n - 1`
+    /// after it, so a list of `0..10` strided by 2 yields the even numbers. The `line_index`
+    /// always points at the next item to be read, so a strided scan can be saved and resumed.
+    /// Skipping wraps around for a `round_robin` list. An `n` of 0 is treated as 1.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new((0..10).collect());
+    /// list.stride(3);
+    /// assert_eq!(list.collect::<Vec<i32>>(), [0, 3, 6, 9]);
+    /// ```
+    pub fn stride(&mut self, n: usize) {
+        self.stride = n.max(1);
+    }
+
+    /// Number of items left before the end of the list. For `round_robin` lists this is the
+    /// number left in the current pass.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![2, 3, 4]);
+    /// list.next();
+    /// assert_eq!(list.remaining(), 2);
+    /// ```
+    pub fn remaining(&self) -> usize {
+        let len = lock(&self.vec).len();
+        self.end_index(len).saturating_sub(self.line_index())
+    }
+
+    /// Take every item from the cursor to the end of the list in one call, leaving the cursor at
+    /// the end. A `round_robin` list stops at the end of its current pass.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![2, 3, 4]);
+    /// list.next();
+    /// assert_eq!(list.drain_remaining(), [3, 4]);
+    /// assert_eq!(list.next(), None);
+    /// ```
+    pub fn drain_remaining(&mut self) -> Vec<T> {
+        let remaining = self.strided(Self::remaining(self));
+        let mut items = Vec::with_capacity(remaining);
+        items.extend(self.by_ref().take(remaining));
+        items
+    }
+
+    /// Returns an iterator of overlapping windows of `size` items, sliding forward one item at a
+    /// time. Nothing is yielded when `size` is 0 or larger than the items left.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![1, 2, 3]);
+    /// assert_eq!(list.windows(2).collect::<Vec<_>>(), [vec![1, 2], vec![2, 3]]);
+    /// ```
+    pub fn windows(&mut self, size: usize) -> Windows<'_, Self> {
+        Windows::new(self, size)
+    }
+
+    /// Maps `line_index` to an index in the underlying [Vec], going through the shuffled order
+    /// when there is one.
+    fn source_index(&self, line_index: usize) -> usize {
+        #[cfg(feature = "shuffle")]
+        if let Some(shuffle) = &self.shuffle {
+            return lock(shuffle).index(line_index);
+        }
+
+        line_index
+    }
+
+    /// Whether pass number `cycle`, counting from 0, is within `max_cycles`.
+    fn cycle_allowed(&self, cycle: usize) -> bool {
+        self.max_cycles
+            .map_or(true, |max_cycles| cycle < max_cycles)
+    }
+
+    /// The exclusive index iteration stops at, accounting for items taken by `next_back`.
+    fn end_index(&self, len: usize) -> usize {
+        len.saturating_sub(self.tail_taken.load(Ordering::Relaxed))
+    }
+
+    /// Write the current `line_index` to `path` so it can be restored with [MemoryList::restore_state].
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![2, 3, 4]);
+    /// list.next();
+    /// list.save_state(Path::new("list.state")).unwrap();
+    /// ```
+    pub fn save_state(&self, path: &Path) -> Result<(), IterManError> {
+        write_state(path, &[self.line_index()])
+    }
+
+    /// Read a `line_index` written by [MemoryList::save_state] and seek to it.
+    /// # Errors
+    /// Returns [IterManError::MemoryOutOfBounds] if the list is shorter than the saved position.
+    pub fn restore_state(&mut self, path: &Path) -> Result<(), IterManError> {
+        let state = read_state(path, 1)?;
+        self.seek(state[0])?;
+        Ok(())
+    }
+
+    /// Take a [CursorState] snapshot of the current position.
+    pub fn cursor(&self) -> CursorState {
+        CursorState {
+            line_index: self.line_index(),
+            bytes_offset: None,
+        }
+    }
+
+    /// Seek to a position previously taken with [MemoryList::cursor]. The `bytes_offset` is ignored.
+    pub fn apply_cursor(&mut self, state: CursorState) -> Result<(), IterManError> {
+        self.seek(state.line_index)?;
+        Ok(())
+    }
+
+    /// The current [Position]. The `bytes_offset` is always [None] for a [MemoryList].
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![2, 3, 4]);
+    /// list.next();
+    /// assert_eq!(list.position(), Position { line_index: 1, bytes_offset: None });
+    /// ```
+    pub fn position(&self) -> Position {
+        self.cursor()
+    }
+
+    /// Seek to a [Position] taken with [MemoryList::position]. The `bytes_offset` is ignored.
+    /// # Errors
+    /// Returns [IterManError::MemoryOutOfBounds] if the list is shorter than `position`.
+    pub fn seek_position(&mut self, position: Position) -> Result<(), IterManError> {
+        self.apply_cursor(position)
+    }
+
+    /// Mark the current position so items read after it can be given back with
+    /// [MemoryList::rollback] if they turn out not to be wanted.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryList::new(vec![1, 2, 3]);
+    /// let cp = list.checkpoint();
+    /// let batch: Vec<_> = list.by_ref().take(2).collect();
+    /// if !valid(&batch) {
+    ///     list.rollback(cp).unwrap();
+    /// }
+    /// ```
+    pub fn checkpoint(&self) -> Position {
+        self.position()
+    }
+
+    /// Return to a position taken with [MemoryList::checkpoint], so the items read since are
+    /// read again.
+    /// # Errors
+    /// Returns [IterManError::MemoryOutOfBounds] if the list is shorter than `cp`.
+    pub fn rollback(&mut self, cp: Position) -> Result<(), IterManError> {
+        self.seek_position(cp)
+    }
+
+    /// Consume the list, calling `f` with the [Position] after every item is read, including
+    /// the items of every pass of a `round_robin` list.
+    /// # Examples
+    /// ```no-run
+    /// let list = MemoryList::new(vec![1, 2, 3]);
+    /// for item in list.on_progress(|position| bar.set_position(position.line_index as u64)) {
+    ///     process(item);
+    /// }
+    /// ```
+    pub fn on_progress<F: FnMut(Position)>(mut self, mut f: F) -> impl Iterator<Item = T> {
+        std::iter::from_fn(move || {
+            let item = self.iter()?;
+            f(self.position());
+            Some(item)
+        })
+    }
+
+    /// Consume the list, pairing each item with its 1-based number, which is the `line_index`
+    /// just after it's read. Numbering carries on from wherever the list was seeked to, and
+    /// starts again from 1 each time a `round_robin` list wraps around.
+    /// # Examples
+    /// ```no-run
+    /// let list = MemoryList::new(vec!["a", "b", "c"]).with_seek_to(1);
+    /// assert_eq!(list.numbered().collect::<Vec<_>>(), [(2, "b"), (3, "c")]);
+    /// ```
+    pub fn numbered(mut self) -> impl Iterator<Item = (usize, T)> {
+        std::iter::from_fn(move || {
+            let item = self.iter()?;
+            Some((self.line_index(), item))
+        })
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T: Clone + Send + Sync> MemoryList<T> {
+    /// Calls `f` on every item left in the list, spread across the rayon thread pool. Items are
+    /// processed from the current `line_index` onwards in no particular order, and `round_robin`
+    /// is ignored so each item is visited once. Clones sharing this list see it as exhausted.
+    /// # Examples
+    /// ```no-run
+    /// let total = AtomicUsize::new(0);
+    /// MemoryList::new(vec![1, 2, 3]).par_for_each(|n| {
+    ///     total.fetch_add(n, Ordering::Relaxed);
+    /// });
+    /// assert_eq!(total.into_inner(), 6);
+    /// ```
+    pub fn par_for_each<F: Fn(T) + Sync + Send>(self, f: F) {
+        use rayon::prelude::*;
+
+        let vec = lock(&self.vec);
+        let end_index = self.end_index(vec.len());
+        let indexes = (self.line_index()..end_index)
+            .map(|line_index| self.source_index(line_index))
+            .collect::<Vec<usize>>();
+        self.line_index
+            .store(end_index.max(self.line_index()), Ordering::SeqCst);
+
+        indexes
+            .into_par_iter()
+            .for_each(|index| f(vec[index].clone()));
+    }
+}
+
+impl<T: Clone> ListLike for MemoryList<T> {
+    type Item = T;
+
+    fn iter(&mut self) -> Option<Self::Item> {
+        if self.seek_log.is_some() {
+            return self.logged(Self::iter);
+        }
+        if self.stride > 1 {
+            let stride = std::mem::replace(&mut self.stride, 1);
+            let item = self.iter();
+            if item.is_some() {
+                (1..stride).map_while(|_| self.iter()).for_each(drop);
+            }
+            self.stride = stride;
+            return item;
+        }
+
+        // Hold a single guard so the round robin check, read and increment happen together.
+        let vec = lock(&self.vec);
+        let cycles = self.cycles.load(Ordering::Relaxed);
+        if !self.cycle_allowed(cycles) {
+            return None;
+        }
+
+        let mut line_index = self.line_index();
+        if self.round_robin && line_index >= vec.len() {
+            if !self.cycle_allowed(cycles + 1) {
+                return None;
+            }
+            self.cycles.store(cycles + 1, Ordering::SeqCst);
+            line_index = 0;
+        }
+
+        if line_index >= self.end_index(vec.len()) {
+            return None;
+        }
+
+        let val = vec.get(self.source_index(line_index))?.clone();
+        self.line_index.store(line_index + 1, Ordering::SeqCst);
+
+        // Draw the next order as soon as a pass ends so `peek` sees it too.
+        #[cfg(feature = "shuffle")]
+        if self.round_robin && line_index + 1 >= vec.len() {
+            if let Some(shuffle) = &self.shuffle {
+                lock(shuffle).reshuffle();
+            }
+        }
+
+        Some(val)
+    }
+
+    fn peek(&mut self) -> Option<Self::Item> {
+        let vec = lock(&self.vec);
+        let cycles = self.cycles.load(Ordering::Relaxed);
+        if !self.cycle_allowed(cycles) {
+            return None;
+        }
+
+        let mut line_index = self.line_index();
+        if self.round_robin && line_index >= vec.len() {
+            if !self.cycle_allowed(cycles + 1) {
+                return None;
+            }
+            line_index = 0;
+        }
+
+        if line_index >= self.end_index(vec.len()) {
+            return None;
+        }
+
+        vec.get(self.source_index(line_index)).cloned()
+    }
+}
+
+impl<T: Clone> Iterator for MemoryList<T>
+where
+    T: Clone,
 {
     type Item = T;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        MemoryArrayList::iter(self)
+    fn next(&mut self) -> Option<Self::Item> {
+        MemoryList::iter(self)
+    }
+
+    /// A `round_robin` list never ends unless it has `max_cycles`, so otherwise only an empty one
+    /// reports an upper bound.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.round_robin {
+            let len = lock(&self.vec).len();
+            if len == 0 {
+                return (0, Some(0));
+            }
+
+            let Some(max_cycles) = self.max_cycles else {
+                return (usize::MAX, None);
+            };
+            let cycles = self.cycles.load(Ordering::Relaxed);
+            if cycles >= max_cycles {
+                return (0, Some(0));
+            }
+            let remaining = self.strided(self.remaining() + (max_cycles - cycles - 1) * len);
+            return (remaining, Some(remaining));
+        }
+
+        let remaining = self.strided(self.remaining());
+        (remaining, Some(remaining))
+    }
+}
+
+/// Iterating from the back is unsupported for `round_robin` lists, since there is no end to wrap
+/// around from, and `next_back` always returns [None] for them.
+/// # Examples
+/// ```no-run
+/// let mut list = MemoryList::new(vec![2, 3, 4]);
+/// assert_eq!(list.next_back(), Some(4));
+/// assert_eq!(list.next(), Some(2));
+/// assert_eq!(list.next_back(), Some(3));
+/// assert_eq!(list.next(), None);
+/// ```
+impl<T: Clone> DoubleEndedIterator for MemoryList<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.round_robin {
+            return None;
+        }
+
+        let vec = lock(&self.vec);
+        let end_index = self.end_index(vec.len());
+        if end_index <= self.line_index() {
+            return None;
+        }
+
+        let val = vec[self.source_index(end_index - 1)].clone();
+        self.tail_taken.fetch_add(1, Ordering::SeqCst);
+        Some(val)
+    }
+}
+
+impl<T: Clone> From<Vec<T>> for MemoryList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        Self::new(vec)
+    }
+}
+
+/// Collects into a [MemoryList] with `round_robin` turned off.
+/// # Examples
+/// ```no-run
+/// let list: MemoryList<i32> = (0..3).collect();
+/// assert_eq!(list.collect::<Vec<i32>>(), [0, 1, 2]);
+/// ```
+impl<T: Clone> FromIterator<T> for MemoryList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+/// Iterates a [MemoryList] by reference through a clone, which shares the cursor of the original
+/// list. Items are yielded from the current position and the original list moves along with the
+/// loop, so a `round_robin` list loops forever unless the loop breaks.
+/// # Examples
+/// ```no-run
+/// let list = MemoryList::new(vec![1, 2, 3]);
+/// for item in &list {
+///     println!("{item}");
+/// }
+/// assert_eq!(list.line_index(), 3);
+/// ```
+impl<T: Clone> IntoIterator for &MemoryList<T> {
+    type Item = T;
+    type IntoIter = MemoryList<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.clone()
+    }
+}
+
+/// A [BufferList] is a [ListLike] that reads from a [BufReader].
+/// # Examples
+/// ```no-run
+/// let reader = BufReader::new(Cursor::new("hello\nworld"));
+/// let list = BufferList::new(reader);
+/// assert_eq!(list.collect::<Vec<String>>(), ["hello", "world"]);
+/// ```
+pub struct BufferList<T: Read + Seek> {
+    buf_reader: Arc<Mutex<SharedReader<T>>>,
+    round_robin: bool,
+    line_index: Arc<AtomicUsize>,
+    bytes_offset: Arc<AtomicUsize>,
+    /// How many full passes a `round_robin` list makes before it stops. [None] repeats forever.
+    max_cycles: Option<usize>,
+    /// Number of times a `round_robin` list has rewound to the start.
+    cycles: Arc<AtomicUsize>,
+    delimiter: u8,
+    trim: TrimMode,
+    /// Drop a UTF-8 byte order mark from the start of the stream.
+    strip_bom: bool,
+    /// Read past lines that are empty or only whitespace.
+    skip_blank: bool,
+    /// The byte offset every line starts at, once built by `build_line_index`.
+    line_offsets: Option<Arc<LineOffsets>>,
+    /// Every position transition, once turned on by `with_seek_log`.
+    seek_log: Option<Vec<(Position, Position)>>,
+    /// How many positions each `next` moves forward, set by `stride`.
+    stride: usize,
+}
+
+/// How much is trimmed from each line of a [BufferList] once its delimiter has been stripped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrimMode {
+    /// Keep the line exactly as read.
+    None,
+    /// Strip a single trailing `\r\n`, `\n` or `\r`, keeping any other whitespace.
+    LineEnding,
+    /// Strip all leading and trailing whitespace.
+    #[default]
+    Full,
+}
+
+impl TrimMode {
+    /// The part of `line` left after trimming.
+    fn apply(self, line: &str) -> &str {
+        match self {
+            TrimMode::None => line,
+            TrimMode::LineEnding => ["\r\n", "\n", "\r"]
+                .iter()
+                .find_map(|ending| line.strip_suffix(ending))
+                .unwrap_or(line),
+            TrimMode::Full => line.trim(),
+        }
+    }
+}
+
+impl From<bool> for TrimMode {
+    fn from(trim: bool) -> Self {
+        if trim {
+            TrimMode::Full
+        } else {
+            TrimMode::None
+        }
+    }
+}
+
+/// The UTF-8 encoding of U+FEFF, which some editors write at the start of a text file.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Whether `record` is empty or only whitespace once `delimiter` is stripped. Records that aren't
+/// valid UTF-8 are never blank.
+fn is_blank_record(record: &[u8], delimiter: u8) -> bool {
+    let record = record.strip_suffix(&[delimiter]).unwrap_or(record);
+    std::str::from_utf8(record).is_ok_and(|line| line.trim().is_empty())
+}
+
+/// Removes a UTF-8 byte order mark from the start of `record[start..]`.
+fn drop_bom_at(record: &mut Vec<u8>, start: usize) {
+    if record[start..].starts_with(UTF8_BOM) {
+        record.drain(start..start + UTF8_BOM.len());
+    }
+}
+
+/// Strips `delimiter` from a raw record, then trims it according to `trim`.
+fn record_to_item(
+    mut record: Vec<u8>,
+    delimiter: u8,
+    trim: TrimMode,
+) -> Result<String, IterManError> {
+    if record.last() == Some(&delimiter) {
+        record.pop();
+    }
+
+    let item = String::from_utf8(record)?;
+    match trim {
+        TrimMode::None => Ok(item),
+        trim => Ok(trim.apply(&item).to_string()),
+    }
+}
+
+/// The byte offset every line of a stream starts at, along with the stream length it was built
+/// for so a stream that has since grown or shrunk isn't trusted.
+struct LineOffsets {
+    stream_len: u64,
+    offsets: Vec<usize>,
+}
+
+/// The reader shared by a [BufferList] and its clones, along with the record `peek` read ahead.
+/// Keeping the peeked record behind the reader lock means whichever handle reads next gets it,
+/// and the shared counters only move once it's consumed.
+struct SharedReader<T> {
+    buf: BufReader<T>,
+    peeked: Option<Peeked>,
+}
+
+impl<T> Deref for SharedReader<T> {
+    type Target = BufReader<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buf
+    }
+}
+
+impl<T> DerefMut for SharedReader<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buf
+    }
+}
+
+/// A raw record read ahead by `peek`, exactly as `try_read_raw` would have returned it.
+struct Peeked {
+    record: Vec<u8>,
+    /// The bytes read from the stream for it, including any blank lines skipped before it.
+    bytes_read: usize,
+    /// How the counters move once it's consumed.
+    advance: PeekAdvance,
+}
+
+enum PeekAdvance {
+    /// Lines and bytes to add to the counters.
+    By { lines: usize, bytes: usize },
+    /// A `round_robin` read that wrapped, so the counters start again from the new values.
+    Wrapped {
+        line_index: usize,
+        bytes_offset: usize,
+        cycles: usize,
+    },
+}
+
+/// Clones share the same reader and cursor, so each line is handed to only one of them. That
+/// includes a line read ahead by `peek`, which goes to whichever handle reads next.
+impl<T: Read + Seek> Clone for BufferList<T> {
+    fn clone(&self) -> Self {
+        Self {
+            buf_reader: Arc::clone(&self.buf_reader),
+            round_robin: self.round_robin,
+            line_index: Arc::clone(&self.line_index),
+            bytes_offset: Arc::clone(&self.bytes_offset),
+            max_cycles: self.max_cycles,
+            cycles: Arc::clone(&self.cycles),
+            delimiter: self.delimiter,
+            trim: self.trim,
+            strip_bom: self.strip_bom,
+            skip_blank: self.skip_blank,
+            line_offsets: self.line_offsets.clone(),
+            seek_log: self.seek_log.clone(),
+            stride: self.stride,
+        }
+    }
+}
+
+/// Shows the cursor without touching the reader.
+impl<T: Read + Seek> fmt::Debug for BufferList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferList")
+            .field("round_robin", &self.round_robin)
+            .field("line_index", &self.line_index())
+            .field("bytes_offset", &self.bytes_offset())
+            .finish_non_exhaustive()
+    }
+}
+
+impl BufferList<File> {
+    /// Creates a new [BufferList] over the file at `path`.
+    /// # Examples
+    /// ```no-run
+    /// let list = BufferList::from_path("clients.txt").unwrap();
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::Io] if the file can't be opened.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, IterManError> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+
+    /// Creates a new [BufferList] over the file at `path` with `round_robin` turned on.
+    /// # Errors
+    /// Returns [IterManError::Io] if the file can't be opened.
+    pub fn from_path_round_robin(path: impl AsRef<Path>) -> Result<Self, IterManError> {
+        Ok(Self::new_round_robin(BufReader::new(File::open(path)?)))
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl BufferList<std::io::Cursor<Vec<u8>>> {
+    /// Creates a new [BufferList] over a gzip compressed stream. Gzip streams can't be seeked, so
+    /// the whole stream is decompressed into memory up front, which keeps `seek` and `round_robin`
+    /// working as usual.
+    /// # Examples
+    /// ```no-run
+    /// let list = BufferList::from_gzip(File::open("access.log.gz").unwrap()).unwrap();
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::Io] if the stream isn't valid gzip.
+    pub fn from_gzip<R: Read>(reader: R) -> Result<Self, IterManError> {
+        Ok(Self::new(BufReader::new(decompress_gzip(reader)?)))
+    }
+
+    /// Creates a new [BufferList] over a gzip compressed stream with `round_robin` turned on.
+    /// # Errors
+    /// Returns [IterManError::Io] if the stream isn't valid gzip.
+    pub fn from_gzip_round_robin<R: Read>(reader: R) -> Result<Self, IterManError> {
+        Ok(Self::new_round_robin(BufReader::new(decompress_gzip(
+            reader,
+        )?)))
+    }
+}
+
+#[cfg(feature = "gzip")]
+fn decompress_gzip<R: Read>(reader: R) -> Result<std::io::Cursor<Vec<u8>>, IterManError> {
+    let mut decompressed = vec![];
+    flate2::read::MultiGzDecoder::new(reader).read_to_end(&mut decompressed)?;
+    Ok(std::io::Cursor::new(decompressed))
+}
+
+impl<T: Read + Seek> BufferList<T> {
+    /// Creates a [BufferListBuilder] to configure a [BufferList] over `buf_reader`.
+    /// # Examples
+    /// ```no-run
+    /// let reader = BufReader::new(Cursor::new("a\0 b \0c\0"));
+    /// let list = BufferList::builder(reader)
+    ///     .delimiter(b'\0')
+    ///     .trim(false)
+    ///     .seek_to(1, 2)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(list.collect::<Vec<String>>(), [" b ", "c"]);
+    /// ```
+    pub fn builder(buf_reader: BufReader<T>) -> BufferListBuilder<T> {
+        BufferListBuilder::new(buf_reader)
+    }
+
+    pub fn new(buf_reader: BufReader<T>) -> Self {
+        Self {
+            buf_reader: Arc::new(Mutex::new(SharedReader {
+                buf: buf_reader,
+                peeked: None,
+            })),
+            round_robin: false,
+            line_index: Arc::new(AtomicUsize::new(0)),
+            bytes_offset: Arc::new(AtomicUsize::new(0)),
+            max_cycles: None,
+            cycles: Arc::new(AtomicUsize::new(0)),
+            delimiter: b'\n',
+            trim: TrimMode::Full,
+            strip_bom: true,
+            skip_blank: false,
+            line_offsets: None,
+            seek_log: None,
+            stride: 1,
+        }
+    }
+
+    /// Creates a new [BufferList] with `round_robin` turned on.
+    pub fn new_round_robin(buf_reader: BufReader<T>) -> Self {
+        Self {
+            round_robin: true,
+            ..Self::new(buf_reader)
+        }
+    }
+
+    /// Creates a new [BufferList] over `reader`, wrapped in a [BufReader] that holds `capacity`
+    /// bytes instead of the default 8 KiB. A larger buffer means fewer reads for very long lines
+    /// or high throughput, while lines longer than the buffer are still read whole.
+    /// # Examples
+    /// ```no-run
+    /// let list = BufferList::with_capacity(File::open("access.log").unwrap(), 1024 * 1024);
+    /// ```
+    pub fn with_capacity(reader: T, capacity: usize) -> Self {
+        Self::new(BufReader::with_capacity(capacity, reader))
+    }
+
+    /// Like [BufferList::new], but treats a reader with nothing left to read as a mistake, such
+    /// as a file that was never written, rather than a list that yields nothing.
+    /// # Examples
+    /// ```no-run
+    /// let reader = BufReader::new(File::open("clients.txt").unwrap());
+    /// let list = BufferList::try_new(reader)?;
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::EmptyList] if the reader is empty, or [IterManError::Io] if it
+    /// can't be read.
+    pub fn try_new(mut buf_reader: BufReader<T>) -> Result<Self, IterManError> {
+        if buf_reader.fill_buf()?.is_empty() {
+            return Err(IterManError::EmptyList);
+        }
+        Ok(Self::new(buf_reader))
+    }
+
+    /// Like [BufferList::new_round_robin], but treats an empty reader as a mistake.
+    /// # Errors
+    /// Returns [IterManError::EmptyList] if the reader is empty, or [IterManError::Io] if it
+    /// can't be read.
+    pub fn try_new_round_robin(buf_reader: BufReader<T>) -> Result<Self, IterManError> {
+        Ok(Self {
+            round_robin: true,
+            ..Self::try_new(buf_reader)?
+        })
+    }
+
+    /// Creates a new [BufferList] with `round_robin` turned on that stops after `times` full
+    /// passes over the reader. A `times` of 0 yields nothing and 1 behaves like [BufferList::new].
+    /// # Examples
+    /// ```no-run
+    /// let reader = BufReader::new(Cursor::new("1\n2\n"));
+    /// let list = BufferList::new_round_robin_times(reader, 2);
+    /// assert_eq!(list.collect::<Vec<String>>(), ["1", "2", "1", "2"]);
+    /// ```
+    pub fn new_round_robin_times(buf_reader: BufReader<T>, times: usize) -> Self {
+        Self {
+            max_cycles: Some(times),
+            ..Self::new_round_robin(buf_reader)
+        }
+    }
+
+    /// Build a [BufferList]] and set the initial `line_index` and `bytes_offset` pointers.
+    /// # Examples
+    /// ```no-run
+    /// let reader = BufReader::new(Cursor::new("hello\nworld"));
+    /// let list = StreamList::new(reader).with_seek_to(1, 6);
+    /// ```
+    pub fn with_seek_to(mut self, line_index: usize, bytes_offset: usize) -> Self {
+        self.seek(line_index, bytes_offset).unwrap_or_default();
+        self
+    }
+
+    /// Build a [BufferList] that keeps leading and trailing whitespace when `trim` is false. Only
+    /// the trailing delimiter is stripped from each line, so a `\r` from `\r\n` line endings is
+    /// kept. Trimming is on by default.
+    /// # Examples
+    /// ```no-run
+    /// let reader = BufReader::new(Cursor::new("  spaced  \n"));
+    /// let list = BufferList::new(reader).with_trim(false);
+    /// assert_eq!(list.collect::<Vec<String>>(), ["  spaced  "]);
+    /// ```
+    pub fn with_trim(mut self, trim: bool) -> Self {
+        self.trim = trim.into();
+        self
+    }
+
+    /// Build a [BufferList] that trims each line according to `trim_mode`. Use
+    /// [TrimMode::LineEnding] to strip a `\r\n` line ending while keeping meaningful indentation.
+    /// # Examples
+    /// ```no-run
+    /// let reader = BufReader::new(Cursor::new("  indented\r\n"));
+    /// let list = BufferList::new(reader).with_trim_mode(TrimMode::LineEnding);
+    /// assert_eq!(list.collect::<Vec<String>>(), ["  indented"]);
+    /// ```
+    pub fn with_trim_mode(mut self, trim_mode: TrimMode) -> Self {
+        self.trim = trim_mode;
+        self
+    }
+
+    /// Build a [BufferList] that splits records on `delimiter` instead of `\n`. The delimiter is
+    /// always stripped from the end of a record, and with `trim` the surrounding whitespace is
+    /// removed as well. The `line_index` then counts records rather than lines.
+    /// # Examples
+    /// ```no-run
+    /// let reader = BufReader::new(Cursor::new("a b\0c\0"));
+    /// let list = BufferList::new(reader).with_delimiter(b'\0', false);
+    /// assert_eq!(list.collect::<Vec<String>>(), ["a b", "c"]);
+    /// ```
+    pub fn with_delimiter(mut self, delimiter: u8, trim: bool) -> Self {
+        self.delimiter = delimiter;
+        self.trim = trim.into();
+        self
+    }
+
+    /// Build a [BufferList] that keeps a UTF-8 byte order mark (`EF BB BF`) at the start of the
+    /// stream when `strip_bom` is false. Stripping is on by default, so files saved by Windows
+    /// tools don't yield a first line with a hidden prefix. The stripped bytes still count
+    /// towards `bytes_offset`, so saved positions stay valid either way.
+    /// # Examples
+    /// ```no-run
+    /// let reader = BufReader::new(Cursor::new("\u{feff}first\n"));
+    /// let list = BufferList::new(reader).with_strip_bom(false);
+    /// assert_eq!(list.collect::<Vec<String>>(), ["\u{feff}first"]);
+    /// ```
+    pub fn with_strip_bom(mut self, strip_bom: bool) -> Self {
+        self.strip_bom = strip_bom;
+        self
+    }
+
+    /// Build a [BufferList] that reads past lines that are empty or only whitespace when
+    /// `skip_blank` is true, instead of yielding them as `""`. Skipped lines still count towards
+    /// `line_index` and `bytes_offset`, so positions keep matching the file. A `round_robin`
+    /// list where every line is blank yields nothing. Off by default.
+    /// # Examples
+    /// ```no-run
+    /// let reader = BufReader::new(Cursor::new("a\n\n  \nb\n"));
+    /// let list = BufferList::new(reader).with_skip_blank(true);
+    /// assert_eq!(list.collect::<Vec<String>>(), ["a", "b"]);
+    /// ```
+    pub fn with_skip_blank(mut self, skip_blank: bool) -> Self {
+        self.skip_blank = skip_blank;
+        self
+    }
+
+    /// Used internally to manage the line index and byte offset
+    fn incr(&self, bytes_read: &usize) {
+        self.line_index.fetch_add(1, Ordering::SeqCst);
+        self.bytes_offset.fetch_add(*bytes_read, Ordering::SeqCst);
+    }
+
+    /// Locks the reader, keeping a poisoned lock as an error, see [lock].
+    fn lock_reader(&self) -> LockResult<MutexGuard<'_, SharedReader<T>>> {
+        self.buf_reader.lock()
+    }
+
+    /// Reset the line index and byte offset, rewinding the reader and dropping any peeked line.
+    /// Like `iter`, a failed rewind isn't reported, so use [BufferList::try_reset] when it matters.
+    pub fn reset(&mut self) {
+        if self.seek_log.is_some() {
+            return self.logged(Self::reset);
+        }
+
+        let mut buf = lock(&self.buf_reader);
+        buf.rewind().unwrap_or_default();
+        buf.peeked = None;
+        self.line_index.store(0, Ordering::Relaxed);
+        self.bytes_offset.store(0, Ordering::Relaxed);
+        self.cycles.store(0, Ordering::Relaxed);
+    }
+
+    /// Like [BufferList::reset], but reports a failed rewind.
+    /// # Errors
+    /// Returns [IterManError::Io] if the reader can't be rewound and [IterManError::Poisoned] if
+    /// another handle panicked mid-read, in which case nothing is reset.
+    pub fn try_reset(&mut self) -> Result<(), IterManError> {
+        if self.seek_log.is_some() {
+            return self.logged(Self::try_reset);
+        }
+
+        let mut buf = self.lock_reader()?;
+        buf.rewind()?;
+        buf.peeked = None;
+        self.line_index.store(0, Ordering::Relaxed);
+        self.bytes_offset.store(0, Ordering::Relaxed);
+        self.cycles.store(0, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Turn `round_robin` on or off without rebuilding the list. Turning it on for a list that
+    /// has been read to the end makes the next read rewind to the first line. Clones keep the
+    /// setting they were created with.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::new(BufReader::new(Cursor::new("a\nb\n")));
+    /// assert_eq!(list.by_ref().count(), 2);
+    /// list.set_round_robin(true);
+    /// assert_eq!(list.next(), Some("a".to_string()));
+    /// ```
+    pub fn set_round_robin(&mut self, round_robin: bool) {
+        self.round_robin = round_robin;
+    }
+
+    /// Make every `next` and `try_next` move forward `n` lines, yielding one line and reading
+    /// past the `n - 1` after it without building [String]s for them. The `line_index` and
+    /// `bytes_offset` always point at the next line to be read, so a strided scan can be saved
+    /// and resumed. Skipping wraps around for a `round_robin` list. An `n` of 0 is treated as 1.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::from_path("huge.txt").unwrap();
+    /// list.stride(1000);
+    /// let sample = list.collect::<Vec<String>>();
+    /// ```
+    pub fn stride(&mut self, n: usize) {
+        self.stride = n.max(1);
+    }
+
+    pub fn seek(&mut self, line_index: usize, bytes_offset: usize) -> Result<usize, IterManError> {
+        if self.seek_log.is_some() {
+            return self.logged(|list| list.seek(line_index, bytes_offset));
+        }
+
+        // https://doc.rust-lang.org/stable/std/io/trait.Seek.html#method.stream_len
+        let stream_len = match self.lock_reader()?.seek(SeekFrom::End(0)).ok() {
+            None => {
+                return Err(IterManError::StreamOutOfBounds {
+                    line_index,
+                    bytes_offset,
+                    max_len: 0,
+                })
+            }
+            Some(len) => len,
+        };
+
+        if stream_len < bytes_offset as u64 {
+            return Err(IterManError::StreamOutOfBounds {
+                line_index,
+                bytes_offset,
+                max_len: stream_len as usize,
+            });
+        }
+
+        let mut buf = self.lock_reader()?;
+        if buf.seek(SeekFrom::Start(bytes_offset as u64)).is_ok() {
+            buf.peeked = None;
+            self.line_index.store(line_index, Ordering::Relaxed);
+            self.bytes_offset.store(bytes_offset, Ordering::Relaxed);
+            return Ok(bytes_offset);
+        }
+
+        Err(IterManError::StreamOutOfBounds {
+            line_index,
+            bytes_offset,
+            max_len: stream_len as usize,
+        })
+    }
+
+    pub fn line_index(&self) -> usize {
+        self.line_index.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes_offset(&self) -> usize {
+        self.bytes_offset.load(Ordering::Relaxed)
+    }
+
+    /// Write the current `line_index` and `bytes_offset` to `path` so they can be restored with
+    /// [BufferList::restore_state].
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::new(BufReader::new(File::open("big.txt").unwrap()));
+    /// list.next();
+    /// list.save_state(Path::new("big.state")).unwrap();
+    /// ```
+    pub fn save_state(&self, path: &Path) -> Result<(), IterManError> {
+        write_state(path, &[self.line_index(), self.bytes_offset()])
+    }
+
+    /// Read the position written by [BufferList::save_state] and seek to it.
+    /// # Errors
+    /// Returns [IterManError::StreamOutOfBounds] if the stream is shorter than the saved offset.
+    pub fn restore_state(&mut self, path: &Path) -> Result<(), IterManError> {
+        let state = read_state(path, 2)?;
+        self.seek(state[0], state[1])?;
+        Ok(())
+    }
+
+    /// Take a [CursorState] snapshot of the current position.
+    pub fn cursor(&self) -> CursorState {
+        CursorState {
+            line_index: self.line_index(),
+            bytes_offset: Some(self.bytes_offset()),
+        }
+    }
+
+    /// Seek to a position previously taken with [BufferList::cursor]. When `bytes_offset` is
+    /// missing the stream is read from the start until `line_index` is reached.
+    pub fn apply_cursor(&mut self, state: CursorState) -> Result<(), IterManError> {
+        match state.bytes_offset {
+            Some(bytes_offset) => self.seek(state.line_index, bytes_offset)?,
+            None => self.seek_to_line(state.line_index)?,
+        };
+        Ok(())
+    }
+
+    /// The current `line_index` and `bytes_offset` as a single [Position].
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::new(BufReader::new(Cursor::new("hello\nworld")));
+    /// list.next();
+    /// assert_eq!(list.position(), Position { line_index: 1, bytes_offset: Some(6) });
+    /// ```
+    pub fn position(&self) -> Position {
+        self.cursor()
+    }
+
+    /// Build a [BufferList] that records the position before and after every `seek`,
+    /// `seek_to_line`, `seek_by`, `seek_percent`, `reset` and `next`, for working out where a
+    /// cursor jumped. Read the log with [BufferList::seek_history]. Lists without it skip the
+    /// bookkeeping entirely.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::from_path("clients.txt").unwrap().with_seek_log();
+    /// list.seek_to_line(10).unwrap();
+    /// list.next();
+    /// println!("{:?}", list.seek_history());
+    /// ```
+    pub fn with_seek_log(mut self) -> Self {
+        self.seek_log = Some(Vec::new());
+        self
+    }
+
+    /// The `(from, to)` positions recorded since [BufferList::with_seek_log], oldest first. Empty
+    /// when the log is off.
+    pub fn seek_history(&self) -> &[(Position, Position)] {
+        self.seek_log.as_deref().unwrap_or_default()
+    }
+
+    /// Run `f` and record the position it moved the list between. The log is taken out while `f`
+    /// runs, so the seeks `f` makes along the way aren't recorded separately.
+    fn logged<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let Some(mut seek_log) = self.seek_log.take() else {
+            return f(self);
+        };
+        let from = Self::position(self);
+        let result = f(self);
+        seek_log.push((from, Self::position(self)));
+        self.seek_log = Some(seek_log);
+        result
+    }
+
+    /// Seek to a [Position], reading from the start of the stream to find the line when it has
+    /// no `bytes_offset`.
+    /// # Errors
+    /// Returns [IterManError::StreamOutOfBounds] if the stream is shorter than `position`.
+    pub fn seek_position(&mut self, position: Position) -> Result<(), IterManError> {
+        self.apply_cursor(position)
+    }
+
+    /// Mark the current position so lines read after it can be given back with
+    /// [BufferList::rollback] if they turn out not to be wanted.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::from_path("clients.txt").unwrap();
+    /// let cp = list.checkpoint();
+    /// let batch: Vec<_> = list.by_ref().take(2).collect();
+    /// if !valid(&batch) {
+    ///     list.rollback(cp).unwrap();
+    /// }
+    /// ```
+    pub fn checkpoint(&self) -> Position {
+        self.position()
+    }
+
+    /// Return to a position taken with [BufferList::checkpoint], restoring its `line_index` and
+    /// `bytes_offset` exactly, so the lines read since are read again.
+    /// # Errors
+    /// Returns [IterManError::StreamOutOfBounds] if the stream is shorter than `cp`.
+    pub fn rollback(&mut self, cp: Position) -> Result<(), IterManError> {
+        self.seek_position(cp)
+    }
+
+    /// Consume the list, calling `f` with the [Position] after every line is read, including
+    /// the lines of every pass of a `round_robin` list.
+    /// # Examples
+    /// ```no-run
+    /// let list = BufferList::from_path("clients.txt").unwrap();
+    /// for line in list.on_progress(|position| bar.set_position(position.line_index as u64)) {
+    ///     send(line);
+    /// }
+    /// ```
+    pub fn on_progress<F: FnMut(Position)>(mut self, mut f: F) -> impl Iterator<Item = String> {
+        std::iter::from_fn(move || {
+            let line = self.iter()?;
+            f(self.position());
+            Some(line)
+        })
+    }
+
+    /// Consume the list, dropping comment lines: those that start with `prefix` once leading
+    /// whitespace is trimmed. An empty `prefix` drops nothing. Combine with
+    /// [BufferList::with_skip_blank] to drop blank lines as well, as `.conf` and `.env` parsers do.
+    /// # Examples
+    /// ```no-run
+    /// let reader = BufReader::new(Cursor::new("# db\nhost=local\n  # port\nport=5432\n"));
+    /// let list = BufferList::new(reader);
+    /// assert_eq!(
+    ///     list.skip_comments("#").collect::<Vec<String>>(),
+    ///     ["host=local", "port=5432"]
+    /// );
+    /// ```
+    pub fn skip_comments(self, prefix: &str) -> impl Iterator<Item = String> {
+        let prefix = prefix.to_string();
+        self.filter(move |line| prefix.is_empty() || !line.trim_start().starts_with(&prefix))
+    }
+
+    /// Consume the list, pairing each line with its 1-based line number like `cat -n`, which is
+    /// the `line_index` just after it's read. Numbering carries on from wherever the list was
+    /// seeked to, and starts again from 1 each time a `round_robin` list wraps around.
+    /// # Examples
+    /// ```no-run
+    /// let list = BufferList::from_path("app.log").unwrap();
+    /// for (number, line) in list.numbered() {
+    ///     println!("{number:>6}\t{line}");
+    /// }
+    /// ```
+    pub fn numbered(mut self) -> impl Iterator<Item = (usize, String)> {
+        std::iter::from_fn(move || {
+            let line = self.iter()?;
+            Some((self.line_index(), line))
+        })
+    }
+
+    /// Seek to the start of line `line_index` by reading from the start of the stream and counting
+    /// newlines, returning the resulting `bytes_offset`. This is O(n) in the size of the stream up
+    /// to that line, so prefer [BufferList::seek] when the byte offset is already known, or call
+    /// [BufferList::build_line_index] first to make it O(1).
+    /// # Examples
+    /// ```no-run
+    /// let reader = BufReader::new(Cursor::new("hello\nworld"));
+    /// let mut list = BufferList::new(reader);
+    /// assert_eq!(list.seek_to_line(1).unwrap(), 6);
+    /// assert_eq!(list.next(), Some("world".to_string()));
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::StreamOutOfBounds] if the stream has fewer lines than `line_index`,
+    /// leaving the previous position in place.
+    pub fn seek_to_line(&mut self, line_index: usize) -> Result<usize, IterManError> {
+        if self.seek_log.is_some() {
+            return self.logged(|list| list.seek_to_line(line_index));
+        }
+
+        let (prev_line_index, prev_bytes_offset) = (self.line_index(), self.bytes_offset());
+        if let Some(line_offsets) = self.line_offsets.clone() {
+            let stream_len = self.lock_reader()?.seek(SeekFrom::End(0))?;
+            if stream_len == line_offsets.stream_len {
+                let bytes_offset = match line_offsets.offsets.get(line_index) {
+                    Some(&bytes_offset) => bytes_offset,
+                    None if line_index == line_offsets.offsets.len() => stream_len as usize,
+                    None => {
+                        self.seek(prev_line_index, prev_bytes_offset)?;
+                        return Err(IterManError::StreamOutOfBounds {
+                            line_index,
+                            bytes_offset: stream_len as usize,
+                            max_len: stream_len as usize,
+                        });
+                    }
+                };
+                return self.seek(line_index, bytes_offset);
+            }
+            self.line_offsets = None;
+        }
+
+        self.seek(0, 0)?;
+
+        let mut line = Vec::new();
+        while self.line_index() < line_index {
+            line.clear();
+            let bytes_read = self
+                .buf_reader
+                .lock()?
+                .read_until(self.delimiter, &mut line)?;
+            if bytes_read == 0 {
+                let max_len = self.bytes_offset();
+                self.seek(prev_line_index, prev_bytes_offset)?;
+                return Err(IterManError::StreamOutOfBounds {
+                    line_index,
+                    bytes_offset: max_len,
+                    max_len,
+                });
+            }
+            self.incr(&bytes_read);
+        }
+
+        Ok(self.bytes_offset())
+    }
+
+    /// Consume the list and return the underlying [BufReader], left wherever the list last read
+    /// to. A line read ahead by `peek` is lost.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::new(BufReader::new(Cursor::new("hello\nworld")));
+    /// list.next();
+    /// let mut rest = String::new();
+    /// list.into_inner().read_to_string(&mut rest).unwrap();
+    /// assert_eq!(rest, "world");
+    /// ```
+    /// # Panics
+    /// Panics if a clone of this list is still alive, since clones share the same reader.
+    pub fn into_inner(self) -> BufReader<T> {
+        Arc::try_unwrap(self.buf_reader)
+            .unwrap_or_else(|_| panic!("BufferList::into_inner called while a clone is alive"))
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+            .buf
+    }
+
+    /// The length of the stream in bytes, leaving the reader where it was.
+    pub(crate) fn stream_len(&self) -> Result<usize, IterManError> {
+        let mut buf = lock(&self.buf_reader);
+        let pos = buf.stream_position()?;
+        let len = buf.seek(SeekFrom::End(0))?;
+        buf.seek(SeekFrom::Start(pos))?;
+        Ok(len as usize)
+    }
+
+    /// Count every line in the stream by reading it from start to end, then put the reader back
+    /// where it was so iteration carries on unchanged. This is O(n) in the size of the stream.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::new(BufReader::new(Cursor::new("a\nb\nc")));
+    /// list.next();
+    /// assert_eq!(list.count_lines().unwrap(), 3);
+    /// assert_eq!(list.next(), Some("b".to_string()));
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::Io] if the stream can't be read, or
+    /// [IterManError::StreamOutOfBounds] if the previous position can't be restored.
+    pub fn count_lines(&mut self) -> Result<usize, IterManError> {
+        let mut count = 0;
+        self.scan_lines(|_| count += 1)?;
+        Ok(count)
+    }
+
+    /// Collect the remaining lines into a [Vec] sized up front from [BufferList::count_lines],
+    /// so a large file is read without the [Vec] reallocating as it grows. Counting costs an
+    /// extra pass over the stream, and if it fails the [Vec] simply grows as usual. Like
+    /// `collect`, this never returns for a `round_robin` list without `max_cycles`.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::from_path("clients.txt").unwrap();
+    /// let lines = list.collect_preallocated();
+    /// ```
+    pub fn collect_preallocated(&mut self) -> Vec<String> {
+        let remaining = self
+            .count_lines()
+            .map_or(0, |count| count.saturating_sub(self.line_index()));
+        let mut lines = Vec::with_capacity(remaining);
+        lines.extend(self.by_ref());
+        lines
+    }
+
+    /// Take every line from the cursor to the end of the stream in one call, leaving the cursor
+    /// at the end. Unlike [BufferList::collect_preallocated] only the remaining lines are read,
+    /// so the [Vec] is sized up front only when [BufferList::build_line_index] has already
+    /// counted them. A `round_robin` list stops at the end of its current pass rather than
+    /// wrapping, and keeps `round_robin` for later reads.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::new(BufReader::new(Cursor::new("a\nb\nc")));
+    /// list.next();
+    /// assert_eq!(list.drain_remaining(), ["b", "c"]);
+    /// assert_eq!(list.next(), None);
+    /// ```
+    pub fn drain_remaining(&mut self) -> Vec<String> {
+        let remaining = self.line_offsets.as_ref().map_or(0, |line_offsets| {
+            line_offsets.offsets.len().saturating_sub(self.line_index())
+        });
+        let round_robin = std::mem::replace(&mut self.round_robin, false);
+        let mut lines = Vec::with_capacity(remaining);
+        lines.extend(self.by_ref());
+        self.round_robin = round_robin;
+        lines
+    }
+
+    /// Read the whole stream once, recording the byte offset every line starts at, so that
+    /// [BufferList::seek_to_line] becomes O(1). The reader is put back where it was afterwards.
+    /// The index is shared with clones and dropped if the stream length changes.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::from_path("clients.txt").unwrap();
+    /// list.build_line_index().unwrap();
+    /// list.seek_to_line(1_000_000).unwrap();
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::Io] if the stream can't be read, or
+    /// [IterManError::StreamOutOfBounds] if the previous position can't be restored.
+    pub fn build_line_index(&mut self) -> Result<(), IterManError> {
+        let mut offsets = Vec::new();
+        let stream_len = self.scan_lines(|bytes_offset| offsets.push(bytes_offset))?;
+        self.line_offsets = Some(Arc::new(LineOffsets {
+            stream_len: stream_len as u64,
+            offsets,
+        }));
+        Ok(())
+    }
+
+    /// Read every line from the start of the stream, passing the byte offset each one starts at
+    /// to `on_line`, then put the reader back where it was. Returns the length of the stream.
+    fn scan_lines(&mut self, mut on_line: impl FnMut(usize)) -> Result<usize, IterManError> {
+        self.restoring_position(|list| {
+            let mut stream_len = 0;
+            let mut buf = list.lock_reader()?;
+            buf.seek(SeekFrom::Start(0))?;
+            let mut line = Vec::new();
+            loop {
+                let bytes_read = buf.read_until(list.delimiter, &mut line)?;
+                if bytes_read == 0 {
+                    break;
+                }
+                on_line(stream_len);
+                stream_len += bytes_read;
+                line.clear();
+            }
+            Ok(stream_len)
+        })
+    }
+
+    /// Read lines `start..end` without moving the list, for showing one page of a large file.
+    /// The list seeks with [BufferList::seek_to_line], so it's O(1) once
+    /// [BufferList::build_line_index] has been called, and is put back where it was afterwards.
+    /// Fewer lines are returned when `end` is past the end of the stream, and none when `start`
+    /// is.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::new(BufReader::new(Cursor::new("a\nb\nc\nd\n")));
+    /// assert_eq!(list.lines_between(1, 3).unwrap(), ["b", "c"]);
+    /// assert_eq!(list.next(), Some("a".to_string()));
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::InvalidRange] if `start` is after `end`, [IterManError::Io] or
+    /// [IterManError::Utf8] if a line can't be read, or [IterManError::StreamOutOfBounds] if
+    /// the previous position can't be restored.
+    pub fn lines_between(&mut self, start: usize, end: usize) -> Result<Vec<String>, IterManError> {
+        if start > end {
+            return Err(IterManError::InvalidRange { start, end });
+        }
+
+        self.restoring_position(|list| {
+            match list.seek_to_line(start) {
+                Err(IterManError::StreamOutOfBounds { .. }) => return Ok(vec![]),
+                result => result?,
+            };
+
+            let mut lines = Vec::with_capacity(end - start);
+            let mut at_start = list.bytes_offset() == 0;
+            for _ in start..end {
+                let mut record = Vec::new();
+                if list
+                    .buf_reader
+                    .lock()?
+                    .read_until(list.delimiter, &mut record)?
+                    == 0
+                {
+                    break;
+                }
+                if std::mem::take(&mut at_start) {
+                    list.drop_bom(&mut record, 0);
+                }
+                lines.push(list.try_to_item(record)?);
+            }
+            Ok(lines)
+        })
+    }
+
+    /// Run `f`, which may move the reader anywhere, then put the list back where it was,
+    /// including any line read ahead by `peek`.
+    fn restoring_position<R>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<R, IterManError>,
+    ) -> Result<R, IterManError> {
+        // The list ends up where it started, so the moves made here aren't worth logging.
+        let seek_log = self.seek_log.take();
+        let (prev_line_index, prev_bytes_offset) = (self.line_index(), self.bytes_offset());
+        // A peeked line has already been read, so the reader sits just after it.
+        let peeked = self.lock_reader()?.peeked.take();
+        let (reader_line_index, reader_bytes_offset) = match peeked.as_ref().map(|p| &p.advance) {
+            Some(PeekAdvance::By { lines, bytes }) => {
+                (prev_line_index + lines, prev_bytes_offset + bytes)
+            }
+            Some(PeekAdvance::Wrapped {
+                line_index,
+                bytes_offset,
+                ..
+            }) => (*line_index, *bytes_offset),
+            None => (prev_line_index, prev_bytes_offset),
+        };
+
+        let result = f(self);
+
+        self.seek(reader_line_index, reader_bytes_offset)?;
+        if peeked.is_some() {
+            let mut buf = self.lock_reader()?;
+            self.line_index.store(prev_line_index, Ordering::Relaxed);
+            self.bytes_offset
+                .store(prev_bytes_offset, Ordering::Relaxed);
+            buf.peeked = peeked;
+        }
+        self.seek_log = seek_log;
+        result
+    }
+
+    /// Read and discard up to `n` lines, returning how many were skipped. The line buffer is
+    /// reused and no [String]s are built, so this is cheaper than calling `next` `n` times. The
+    /// `line_index` and `bytes_offset` are updated as if each line was read, and a `round_robin`
+    /// list wraps around like it does for `iter`. The count is in lines and ignores `stride`.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::new(BufReader::new(Cursor::new("a\nb\nc\n")));
+    /// assert_eq!(list.advance(2).unwrap(), 2);
+    /// assert_eq!(list.bytes_offset(), 4);
+    /// assert_eq!(list.next(), Some("c".to_string()));
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::Io] if the stream can't be read.
+    pub fn advance(&mut self, n: usize) -> Result<usize, IterManError> {
+        let mut skipped = 0;
+        let mut record = Vec::new();
+        while skipped < n {
+            record.clear();
+            if self.try_read_raw(&mut record)?.is_none() {
+                break;
+            }
+            skipped += 1;
+        }
+
+        Ok(skipped)
+    }
+
+    /// Move forward, or backward for a negative `delta`, by a number of lines and return the new
+    /// `bytes_offset`. Moving forward reads ahead from the current position, while moving backward
+    /// rescans from the start of the stream like [BufferList::seek_to_line]. Moving back past the
+    /// start clamps to the first line.
+    /// # Examples
+    /// ```no-run
+    /// let reader = BufReader::new(Cursor::new("a\nb\nc\n"));
+    /// let mut list = BufferList::new(reader);
+    /// assert_eq!(list.seek_by(2).unwrap(), 4);
+    /// assert_eq!(list.seek_by(-1).unwrap(), 2);
+    /// assert_eq!(list.next(), Some("b".to_string()));
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::StreamOutOfBounds] when moving past the end of the stream, leaving
+    /// the previous position in place.
+    pub fn seek_by(&mut self, delta: isize) -> Result<usize, IterManError> {
+        if self.seek_log.is_some() {
+            return self.logged(|list| list.seek_by(delta));
+        }
+
+        let (prev_line_index, prev_bytes_offset) = (self.line_index(), self.bytes_offset());
+        if delta < 0 {
+            return self.seek_to_line(prev_line_index.saturating_add_signed(delta));
+        }
+
+        // Re-seeking drops any peeked line so the reader is back at the current position.
+        self.seek(prev_line_index, prev_bytes_offset)?;
+
+        let mut line = Vec::new();
+        for _ in 0..delta {
+            line.clear();
+            let bytes_read = self
+                .buf_reader
+                .lock()?
+                .read_until(self.delimiter, &mut line)?;
+            if bytes_read == 0 {
+                let max_len = self.bytes_offset();
+                self.seek(prev_line_index, prev_bytes_offset)?;
+                return Err(IterManError::StreamOutOfBounds {
+                    line_index: prev_line_index + delta as usize,
+                    bytes_offset: max_len,
+                    max_len,
+                });
+            }
+            self.incr(&bytes_read);
+        }
+
+        Ok(self.bytes_offset())
+    }
+
+    /// Seek to the first line starting at or after `pct` of the way through the stream, returning
+    /// the resulting `bytes_offset`. The stream is read from the start so the `line_index` stays
+    /// accurate, which makes this O(n) like [BufferList::seek_to_line]. Splitting a file into
+    /// shards by percentage ranges never cuts a line in half.
+    /// # Examples
+    /// ```no-run
+    /// let reader = BufReader::new(Cursor::new("hello\nworld\n"));
+    /// let mut list = BufferList::new(reader);
+    /// assert_eq!(list.seek_percent(0.25).unwrap(), 6);
+    /// assert_eq!(list.next(), Some("world".to_string()));
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::InvalidPercent] if `pct` is outside `0.0..=1.0`.
+    pub fn seek_percent(&mut self, pct: f64) -> Result<usize, IterManError> {
+        if self.seek_log.is_some() {
+            return self.logged(|list| list.seek_percent(pct));
+        }
+
+        if !(0.0..=1.0).contains(&pct) {
+            return Err(IterManError::InvalidPercent(pct));
+        }
+
+        let stream_len = self.lock_reader()?.seek(SeekFrom::End(0))?;
+        let target = (stream_len as f64 * pct) as usize;
+        self.seek(0, 0)?;
+
+        let mut line = Vec::new();
+        while self.bytes_offset() < target {
+            line.clear();
+            let bytes_read = self
+                .buf_reader
+                .lock()?
+                .read_until(self.delimiter, &mut line)?;
+            if bytes_read == 0 {
+                break;
+            }
+            self.incr(&bytes_read);
+        }
+
+        Ok(self.bytes_offset())
+    }
+
+    /// Picks a uniform random sample of up to `k` lines from the current position to EOF using
+    /// reservoir sampling, so only `k` lines are held in memory at once. The same `seed` always
+    /// gives the same sample. The list is left at EOF, even when `round_robin` is on.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::from_path("huge.txt").unwrap();
+    /// let sample = list.sample(3, 42);
+    /// ```
+    #[cfg(feature = "shuffle")]
+    pub fn sample(&mut self, k: usize, seed: u64) -> Vec<String> {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let mut reservoir = Vec::with_capacity(k);
+        let mut seen = 0;
+
+        // Read to EOF without wrapping, with the same BOM and blank line handling as `iter`.
+        let round_robin = std::mem::replace(&mut self.round_robin, false);
+        let mut record = Vec::new();
+        while self.read_raw(&mut record).is_some() {
+            let Some(line) = self.to_item(std::mem::take(&mut record)) else {
+                continue;
+            };
+            if reservoir.len() < k {
+                reservoir.push(line);
+            } else {
+                let j = rng.gen_range(0..=seen);
+                if j < k {
+                    reservoir[j] = line;
+                }
+            }
+            seen += 1;
+        }
+        self.round_robin = round_robin;
+
+        reservoir.truncate(k);
+        reservoir
+    }
+
+    /// Consume the list and parse each line into `F`, yielding the parse error for lines that
+    /// don't parse, including empty ones for most types, instead of stopping or panicking.
+    /// # Examples
+    /// ```no-run
+    /// let list = BufferList::new(BufReader::new(Cursor::new("1\n2\n")));
+    /// assert_eq!(list.parse_lines::<i32>().collect::<Vec<_>>(), [Ok(1), Ok(2)]);
+    /// ```
+    pub fn parse_lines<F: FromStr>(self) -> impl Iterator<Item = Result<F, F::Err>> {
+        self.map(|line| line.parse::<F>())
+    }
+
+    /// Consume the list and deserialize each line as a JSON value, for reading JSON-lines files.
+    /// Malformed lines yield an error and iteration carries on with the next line.
+    /// # Examples
+    /// ```no-run
+    /// #[derive(Deserialize)]
+    /// struct Client { name: String }
+    ///
+    /// let list = BufferList::from_path("clients.jsonl").unwrap();
+    /// for client in list.deserialize_lines::<Client>() {
+    ///     println!("{}", client.unwrap().name);
+    /// }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn deserialize_lines<D: serde::de::DeserializeOwned>(
+        self,
+    ) -> impl Iterator<Item = Result<D, serde_json::Error>> {
+        self.map(|line| serde_json::from_str::<D>(&line))
+    }
+
+    /// Reads the next line into `buf`, appending to whatever is already there, and returns the
+    /// number of bytes read from the stream. Clearing and reusing the same [String] avoids the
+    /// per-line allocation of [ListLike::iter]. The line is stripped and trimmed the same way.
+    ///
+    /// [None] means the end of the stream, but also a failed read or a line that isn't valid
+    /// UTF-8, which is consumed and left out of `buf`. Use [BufferList::try_read_into] to tell
+    /// them apart.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::new(BufReader::new(Cursor::new("hello\nworld")));
+    /// let mut line = String::new();
+    /// while list.read_into(&mut line).is_some() {
+    ///     println!("{line}");
+    ///     line.clear();
+    /// }
+    /// ```
+    pub fn read_into(&mut self, buf: &mut String) -> Option<usize> {
+        self.try_read_into(buf).ok().flatten()
+    }
+
+    /// Like [BufferList::read_into], but returns `Ok(None)` only at the end of the stream.
+    /// # Errors
+    /// [IterManError::Io] for a failed read, [IterManError::Utf8] for a line that isn't valid
+    /// UTF-8 and [IterManError::Poisoned] if another handle panicked mid-read. The line is still
+    /// consumed and `buf` is left as it was.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::from_path("clients.txt").unwrap();
+    /// let mut line = String::new();
+    /// while list.try_read_into(&mut line)?.is_some() {
+    ///     println!("{line}");
+    ///     line.clear();
+    /// }
+    /// ```
+    pub fn try_read_into(&mut self, buf: &mut String) -> Result<Option<usize>, IterManError> {
+        // Read straight onto the end of the caller's buffer, then strip the new part in place.
+        let mut record = std::mem::take(buf).into_bytes();
+        let start = record.len();
+        let bytes_read = self.try_read_raw(&mut record);
+        if record.len() > start && record.last() == Some(&self.delimiter) {
+            record.pop();
+        }
+
+        let line = match &bytes_read {
+            Ok(Some(_)) => std::str::from_utf8(&record[start..]).map_err(|e| e.to_string()),
+            _ => Ok(""),
+        };
+        let (lead, keep) = match line {
+            Ok(line) => {
+                let keep = self.trim.apply(line).len();
+                let lead = match self.trim {
+                    TrimMode::Full => line.len() - line.trim_start().len(),
+                    _ => 0,
+                };
+                (lead, keep)
+            }
+            Err(e) => {
+                record.truncate(start);
+                *buf = String::from_utf8(record).unwrap_or_default();
+                return Err(IterManError::Utf8(e));
+            }
+        };
+        record.truncate(start + lead + keep);
+        record.drain(start..start + lead);
+
+        *buf = String::from_utf8(record).unwrap_or_default();
+        bytes_read
+    }
+
+    /// Iterate over the remaining lines as raw bytes, skipping UTF-8 validation so lines that
+    /// aren't valid UTF-8 come through byte for byte. Only the trailing delimiter is stripped,
+    /// `trim` is ignored, and the cursor and `round_robin` work the same as for `iter`. A line
+    /// read ahead by `peek` comes first, just as it was read.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::new(BufReader::new(Cursor::new(b"a\xff\nb\n".to_vec())));
+    /// assert_eq!(list.bytes().collect::<Vec<_>>(), [b"a\xff".to_vec(), b"b".to_vec()]);
+    /// ```
+    pub fn bytes(&mut self) -> impl Iterator<Item = Vec<u8>> + '_ {
+        std::iter::from_fn(move || {
+            let mut record = Vec::new();
+            self.read_raw(&mut record)?;
+            if record.last() == Some(&self.delimiter) {
+                record.pop();
+            }
+            Some(record)
+        })
+    }
+
+    /// Iterate over the rest of the stream as records of exactly `width` bytes, for files of
+    /// fixed-length records with no delimiter. Each record counts as one line for `line_index`,
+    /// and `bytes_offset` moves on by `width`. A shorter record left at the end of the stream is
+    /// yielded as it is; use [BufferList::try_fixed_records] to treat it as an error instead.
+    /// Iteration stops at the end of the stream even with `round_robin` on, and a `width` of 0
+    /// yields nothing.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::new(BufReader::new(Cursor::new("abcdefg")));
+    /// let records = list.fixed_records(3).collect::<Vec<_>>();
+    /// assert_eq!(records, [b"abc".to_vec(), b"def".to_vec(), b"g".to_vec()]);
+    /// ```
+    pub fn fixed_records(&mut self, width: usize) -> impl Iterator<Item = Vec<u8>> + '_ {
+        self.try_fixed_records(width)
+            .map_while(|record| match record {
+                Ok(record) => Some(record),
+                Err(IterManError::PartialRecord { record, .. }) => Some(record),
+                Err(_) => None,
+            })
+    }
+
+    /// Like [BufferList::fixed_records], but yields [IterManError::PartialRecord] for a shorter
+    /// record left at the end of the stream, and [IterManError::Io] if the stream can't be read.
+    /// A line read ahead by `peek` is dropped by seeking back to the start of it, and if that
+    /// fails its error is the only item.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::new(BufReader::new(Cursor::new("abcdefg")));
+    /// for record in list.try_fixed_records(3) {
+    ///     process(record?);
+    /// }
+    /// ```
+    pub fn try_fixed_records(
+        &mut self,
+        width: usize,
+    ) -> impl Iterator<Item = Result<Vec<u8>, IterManError>> + '_ {
+        let mut seek_error = None;
+        if lock(&self.buf_reader).peeked.is_some() {
+            seek_error = self.seek(self.line_index(), self.bytes_offset()).err();
+        }
+
+        let mut failed = false;
+        std::iter::from_fn(move || {
+            if let Some(e) = seek_error.take() {
+                failed = true;
+                return Some(Err(e));
+            }
+            if width == 0 || failed {
+                return None;
+            }
+
+            let mut record = Vec::with_capacity(width);
+            let bytes_read = match self.lock_reader() {
+                Ok(mut buf) => (&mut buf.buf).take(width as u64).read_to_end(&mut record),
+                Err(e) => return Some(Err(e.into())),
+            };
+            match bytes_read {
+                Ok(0) => None,
+                Ok(bytes_read) => {
+                    self.incr(&bytes_read);
+                    if bytes_read < width {
+                        return Some(Err(IterManError::PartialRecord { width, record }));
+                    }
+                    Some(Ok(record))
+                }
+                Err(e) => Some(Err(e.into())),
+            }
+        })
+    }
+
+    /// Reads the next line from the underlying reader, rewinding first if `round_robin` is on.
+    fn read_next(&mut self) -> Option<String> {
+        let mut record = Vec::new();
+        self.read_raw(&mut record)?;
+        self.to_item(record)
+    }
+
+    /// Reads the next raw record onto the end of `record` and updates the counters, returning the
+    /// bytes read. With `round_robin` the reader is rewound at EOF.
+    fn read_raw(&self, record: &mut Vec<u8>) -> Option<usize> {
+        self.try_read_raw(record).ok().flatten()
+    }
+
+    /// Like [BufferList::read_raw], but tells a failed read apart from the end of the stream.
+    fn try_read_raw(&self, record: &mut Vec<u8>) -> Result<Option<usize>, IterManError> {
+        // Hold the reader until the counters are updated so clones never see a line twice.
+        let mut buf = self.lock_reader()?;
+        if let Some(peeked) = buf.peeked.take() {
+            match peeked.advance {
+                PeekAdvance::By { lines, bytes } => {
+                    self.line_index.fetch_add(lines, Ordering::SeqCst);
+                    self.bytes_offset.fetch_add(bytes, Ordering::SeqCst);
+                }
+                PeekAdvance::Wrapped {
+                    line_index,
+                    bytes_offset,
+                    cycles,
+                } => {
+                    self.line_index.store(line_index, Ordering::SeqCst);
+                    self.bytes_offset.store(bytes_offset, Ordering::SeqCst);
+                    self.cycles.store(cycles, Ordering::SeqCst);
+                }
+            }
+            record.extend_from_slice(&peeked.record);
+            return Ok(Some(peeked.bytes_read));
+        }
+        self.read_locked(&mut buf, record)
+    }
+
+    /// Reads the next raw record from the locked reader, skipping blank lines for `skip_blank`.
+    fn read_locked(
+        &self,
+        buf: &mut BufReader<T>,
+        record: &mut Vec<u8>,
+    ) -> Result<Option<usize>, IterManError> {
+        let start = record.len();
+        let first_cycle = self.cycles.load(Ordering::Relaxed);
+        let mut bytes_read = 0;
+        loop {
+            let Some(line_bytes) = self.read_one(buf, record)? else {
+                return Ok(None);
+            };
+            bytes_read += line_bytes;
+            if !self.skip_blank || !self.is_blank(&record[start..]) {
+                return Ok(Some(bytes_read));
+            }
+
+            record.truncate(start);
+            // Two rewinds without finding a line means every line is blank.
+            if self.cycles.load(Ordering::Relaxed) > first_cycle + 1 {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Reads a single record onto the end of `record` from the locked reader, rewinding for
+    /// `round_robin`, and updates the counters.
+    fn read_one(
+        &self,
+        buf: &mut BufReader<T>,
+        record: &mut Vec<u8>,
+    ) -> Result<Option<usize>, IterManError> {
+        let cycles = self.cycles.load(Ordering::Relaxed);
+        let start = record.len();
+        let mut at_start = self.bytes_offset() == 0;
+        if self
+            .max_cycles
+            .is_some_and(|max_cycles| cycles >= max_cycles)
+        {
+            return Ok(None);
+        }
+
+        let mut bytes_read = buf.read_until(self.delimiter, record)?;
+
+        if bytes_read == 0 {
+            if !self.round_robin
+                || self
+                    .max_cycles
+                    .is_some_and(|max_cycles| cycles + 1 >= max_cycles)
+            {
+                return Ok(None);
+            }
+            self.cycles.store(cycles + 1, Ordering::SeqCst);
+
+            buf.seek(SeekFrom::Start(0))?;
+            self.line_index.store(0, Ordering::Relaxed);
+            self.bytes_offset.store(0, Ordering::Relaxed);
+
+            bytes_read = buf.read_until(self.delimiter, record)?;
+            if bytes_read == 0 {
+                return Ok(None); // Needed to stop empty buffer from returning ""
+            }
+            at_start = true;
+        }
+
+        if at_start {
+            self.drop_bom(record, start);
+        }
+        self.incr(&bytes_read);
+        Ok(Some(bytes_read))
+    }
+
+    /// Whether `record` is empty or only whitespace once its delimiter is stripped. Records that
+    /// aren't valid UTF-8 are never blank.
+    fn is_blank(&self, record: &[u8]) -> bool {
+        is_blank_record(record, self.delimiter)
+    }
+
+    /// Removes a UTF-8 byte order mark from `record[start..]`, which must have been read from the
+    /// start of the stream. The counters are left alone, so `bytes_offset` still includes it.
+    fn drop_bom(&self, record: &mut Vec<u8>, start: usize) {
+        if self.strip_bom {
+            drop_bom_at(record, start);
+        }
+    }
+
+    /// Strips the delimiter from a raw record, then trims it according to `trim`.
+    fn to_item(&self, record: Vec<u8>) -> Option<String> {
+        self.try_to_item(record).ok()
+    }
+
+    /// Like [BufferList::to_item], but returns the UTF-8 error instead of dropping it.
+    fn try_to_item(&self, record: Vec<u8>) -> Result<String, IterManError> {
+        record_to_item(record, self.delimiter, self.trim)
+    }
+}
+
+impl<T: Read + Seek> ListLike for BufferList<T> {
+    type Item = String;
+
+    fn iter(&mut self) -> Option<Self::Item> {
+        if self.seek_log.is_some() {
+            return self.logged(Self::iter);
+        }
+        if self.stride > 1 {
+            let stride = std::mem::replace(&mut self.stride, 1);
+            let line = self.iter();
+            if line.is_some() {
+                self.advance(stride - 1).unwrap_or_default();
+            }
+            self.stride = stride;
+            return line;
+        }
+
+        self.read_next()
+    }
+
+    /// Reads the next line, returning [IterManError::Io] for a failed read,
+    /// [IterManError::Utf8] for a line that isn't valid UTF-8 and [IterManError::Poisoned] if
+    /// another handle panicked mid-read, where `iter` would return [None] for all of them.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = BufferList::from_path("clients.txt").unwrap();
+    /// while let Some(line) = list.try_next()? {
+    ///     println!("{line}");
+    /// }
+    /// ```
+    fn try_next(&mut self) -> Result<Option<Self::Item>, IterManError> {
+        if self.seek_log.is_some() {
+            return self.logged(Self::try_next);
+        }
+        if self.stride > 1 {
+            let stride = std::mem::replace(&mut self.stride, 1);
+            let line = self.try_next().and_then(|line| {
+                if line.is_some() {
+                    self.advance(stride - 1)?;
+                }
+                Ok(line)
+            });
+            self.stride = stride;
+            return line;
+        }
+
+        let mut record = Vec::new();
+        if self.try_read_raw(&mut record)?.is_none() {
+            return Ok(None);
+        }
+        self.try_to_item(record).map(Some)
+    }
+
+    /// Reads ahead one line and caches it so the following read returns it, from this handle or
+    /// any clone. The `line_index` and `bytes_offset` are left untouched until the line is
+    /// consumed.
+    fn peek(&mut self) -> Option<Self::Item> {
+        let mut buf = self.lock_reader().ok()?;
+        if buf.peeked.is_none() {
+            let line_index = self.line_index();
+            let bytes_offset = self.bytes_offset();
+            let cycles = self.cycles.load(Ordering::Relaxed);
+            let mut record = Vec::new();
+            let bytes_read = self.read_locked(&mut buf, &mut record).ok().flatten()?;
+
+            // The reader stays locked, so nothing else has moved the counters since.
+            let advance = if self.cycles.load(Ordering::Relaxed) == cycles {
+                PeekAdvance::By {
+                    lines: self.line_index() - line_index,
+                    bytes: self.bytes_offset() - bytes_offset,
+                }
+            } else {
+                PeekAdvance::Wrapped {
+                    line_index: self.line_index(),
+                    bytes_offset: self.bytes_offset(),
+                    cycles: self.cycles.load(Ordering::Relaxed),
+                }
+            };
+            self.line_index.store(line_index, Ordering::SeqCst);
+            self.bytes_offset.store(bytes_offset, Ordering::SeqCst);
+            self.cycles.store(cycles, Ordering::SeqCst);
+            buf.peeked = Some(Peeked {
+                record,
+                bytes_read,
+                advance,
+            });
+        }
+
+        let record = buf.peeked.as_ref()?.record.clone();
+        drop(buf);
+        self.to_item(record)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T: Read + Seek> BufferList<T> {
+    /// Wraps the list in a [BufferStream] for use where a [futures_core::Stream] is expected.
+    /// The reader is still blocking, so every poll blocks the task until a line is read. See
+    /// [BufferStream] for when that's acceptable.
+    /// # Examples
+    /// ```no-run
+    /// use futures::StreamExt;
+    ///
+    /// let list = BufferList::new(BufReader::new(Cursor::new("hello\nworld")));
+    /// let mut stream = list.into_stream();
+    /// assert_eq!(StreamExt::next(&mut stream).await, Some("hello".to_string()));
+    /// ```
+    pub fn into_stream(self) -> BufferStream<T> {
+        BufferStream::new(self)
+    }
+}
+
+impl<T: Read + Seek> Iterator for BufferList<T>
+where
+    T: Read + Seek,
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        BufferList::iter(self)
+    }
+}
+
+/// Builds a [BufferList] from a combination of options, created by [BufferList::builder].
+pub struct BufferListBuilder<T: Read + Seek> {
+    buf_reader: BufReader<T>,
+    round_robin: bool,
+    delimiter: u8,
+    trim: TrimMode,
+    strip_bom: bool,
+    skip_blank: bool,
+    seek_to: Option<(usize, usize)>,
+}
+
+impl<T: Read + Seek> BufferListBuilder<T> {
+    fn new(buf_reader: BufReader<T>) -> Self {
+        Self {
+            buf_reader,
+            round_robin: false,
+            delimiter: b'\n',
+            trim: TrimMode::Full,
+            strip_bom: true,
+            skip_blank: false,
+            seek_to: None,
+        }
+    }
+
+    pub fn round_robin(mut self, round_robin: bool) -> Self {
+        self.round_robin = round_robin;
+        self
+    }
+
+    /// See [BufferList::with_trim].
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim.into();
+        self
+    }
+
+    /// See [BufferList::with_trim_mode].
+    pub fn trim_mode(mut self, trim_mode: TrimMode) -> Self {
+        self.trim = trim_mode;
+        self
+    }
+
+    /// See [BufferList::with_strip_bom].
+    pub fn strip_bom(mut self, strip_bom: bool) -> Self {
+        self.strip_bom = strip_bom;
+        self
+    }
+
+    /// See [BufferList::with_skip_blank].
+    pub fn skip_blank(mut self, skip_blank: bool) -> Self {
+        self.skip_blank = skip_blank;
+        self
+    }
+
+    /// See [BufferList::with_delimiter].
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Start reading from `line_index` and `bytes_offset`, checked when the list is built.
+    pub fn seek_to(mut self, line_index: usize, bytes_offset: usize) -> Self {
+        self.seek_to = Some((line_index, bytes_offset));
+        self
+    }
+
+    /// # Errors
+    /// Returns [IterManError::StreamOutOfBounds] if the `seek_to` position is past the end of the
+    /// stream.
+    pub fn build(self) -> Result<BufferList<T>, IterManError> {
+        let mut list = BufferList {
+            round_robin: self.round_robin,
+            delimiter: self.delimiter,
+            trim: self.trim,
+            strip_bom: self.strip_bom,
+            skip_blank: self.skip_blank,
+            ..BufferList::new(self.buf_reader)
+        };
+        if let Some((line_index, bytes_offset)) = self.seek_to {
+            list.seek(line_index, bytes_offset)?;
+        }
+        Ok(list)
+    }
+}
+
+/// A [ForwardBufferList] is a forward-only [ListLike] over any [Read], such as a pipe, socket or
+/// compressed stream that can't [Seek]. It tracks `line_index` and `bytes_offset` like a
+/// [BufferList], and splits, trims and strips lines the same way, but it can't seek back to a
+/// position or wrap around for `round_robin`.
+/// # Examples
+/// ```no-run
+/// let reader = flate2::read::GzDecoder::new(File::open("clients.txt.gz").unwrap());
+/// let list = ForwardBufferList::from_reader(reader);
+/// for line in list {
+///     println!("{line}");
+/// }
+/// ```
+pub struct ForwardBufferList<R: Read> {
+    buf_reader: BufReader<R>,
+    line_index: AtomicUsize,
+    bytes_offset: AtomicUsize,
+    delimiter: u8,
+    trim: TrimMode,
+    /// Drop a UTF-8 byte order mark from the start of the stream.
+    strip_bom: bool,
+    /// Read past lines that are empty or only whitespace.
+    skip_blank: bool,
+    /// A raw record read ahead by `peek`, along with the lines and bytes it took up.
+    peeked: Option<RawRecord>,
+}
+
+/// A raw record along with the lines and bytes read to get it, including blank lines skipped
+/// before it.
+type RawRecord = (Vec<u8>, usize, usize);
+
+/// Shows the cursor without touching the reader.
+impl<R: Read> fmt::Debug for ForwardBufferList<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ForwardBufferList")
+            .field("line_index", &self.line_index())
+            .field("bytes_offset", &self.bytes_offset())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A [ForwardBufferList] over standard input, for tools used as `cat clients.txt | mytool`.
+/// # Examples
+/// ```no-run
+/// let list = StdinList::new();
+/// for line in list {
+///     println!("{line}");
+/// }
+/// ```
+pub type StdinList = ForwardBufferList<Stdin>;
+
+impl ForwardBufferList<Stdin> {
+    /// Creates a new [StdinList] over standard input.
+    pub fn new() -> Self {
+        Self::from_reader(std::io::stdin())
+    }
+}
+
+impl Default for ForwardBufferList<Stdin> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Read> ForwardBufferList<R> {
+    /// Creates a new [ForwardBufferList] over any reader.
+    pub fn from_reader(reader: R) -> Self {
+        Self {
+            buf_reader: BufReader::new(reader),
+            line_index: AtomicUsize::new(0),
+            bytes_offset: AtomicUsize::new(0),
+            delimiter: b'\n',
+            trim: TrimMode::Full,
+            strip_bom: true,
+            skip_blank: false,
+            peeked: None,
+        }
+    }
+
+    /// Like [BufferList::with_trim].
+    pub fn with_trim(mut self, trim: bool) -> Self {
+        self.trim = trim.into();
+        self
+    }
+
+    /// Like [BufferList::with_trim_mode].
+    pub fn with_trim_mode(mut self, trim_mode: TrimMode) -> Self {
+        self.trim = trim_mode;
+        self
+    }
+
+    /// Like [BufferList::with_delimiter].
+    pub fn with_delimiter(mut self, delimiter: u8, trim: bool) -> Self {
+        self.delimiter = delimiter;
+        self.trim = trim.into();
+        self
+    }
+
+    /// Like [BufferList::with_strip_bom].
+    pub fn with_strip_bom(mut self, strip_bom: bool) -> Self {
+        self.strip_bom = strip_bom;
+        self
+    }
+
+    /// Like [BufferList::with_skip_blank].
+    pub fn with_skip_blank(mut self, skip_blank: bool) -> Self {
+        self.skip_blank = skip_blank;
+        self
+    }
+
+    /// Used internally to manage the line index and byte offset
+    fn incr(&self, lines: usize, bytes_read: usize) {
+        self.line_index.fetch_add(lines, Ordering::SeqCst);
+        self.bytes_offset.fetch_add(bytes_read, Ordering::SeqCst);
+    }
+
+    pub fn line_index(&self) -> usize {
+        self.line_index.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes_offset(&self) -> usize {
+        self.bytes_offset.load(Ordering::Relaxed)
+    }
+
+    /// Fails when turning `round_robin` on, since the reader can't rewind to the start. Turning
+    /// it off is a no-op.
+    /// # Errors
+    /// Returns [IterManError::NotSeekable] if `round_robin` is true.
+    pub fn set_round_robin(&mut self, round_robin: bool) -> Result<(), IterManError> {
+        if round_robin {
+            return Err(IterManError::NotSeekable);
+        }
+        Ok(())
+    }
+
+    /// Always fails, the reader can only move forward.
+    /// # Errors
+    /// Returns [IterManError::NotSeekable].
+    pub fn seek(
+        &mut self,
+        _line_index: usize,
+        _bytes_offset: usize,
+    ) -> Result<usize, IterManError> {
+        Err(IterManError::NotSeekable)
+    }
+
+    /// Reads the next raw record without updating the counters. Blank lines skipped on the way to
+    /// the end of the stream or a failed read are counted straight away, since nothing is left to
+    /// consume them.
+    fn read_raw(&mut self) -> Result<Option<RawRecord>, IterManError> {
+        if let Some(peeked) = self.peeked.take() {
+            return Ok(Some(peeked));
+        }
+
+        let (mut lines, mut bytes) = (0, 0);
+        loop {
+            let mut record = Vec::new();
+            let bytes_read = match self.buf_reader.read_until(self.delimiter, &mut record) {
+                Ok(0) => {
+                    self.incr(lines, bytes);
+                    return Ok(None);
+                }
+                Ok(bytes_read) => bytes_read,
+                Err(e) => {
+                    self.incr(lines, bytes);
+                    return Err(e.into());
+                }
+            };
+
+            if self.strip_bom && self.bytes_offset() + bytes == 0 {
+                drop_bom_at(&mut record, 0);
+            }
+            lines += 1;
+            bytes += bytes_read;
+            if !self.skip_blank || !is_blank_record(&record, self.delimiter) {
+                return Ok(Some((record, lines, bytes)));
+            }
+        }
+    }
+}
+
+impl<R: Read> ListLike for ForwardBufferList<R> {
+    type Item = String;
+
+    fn iter(&mut self) -> Option<Self::Item> {
+        self.try_next().ok().flatten()
+    }
+
+    /// Reads the next line, returning [IterManError::Io] for a failed read and
+    /// [IterManError::Utf8] for a line that isn't valid UTF-8, where `iter` would return [None].
+    /// A line that isn't valid UTF-8 is still consumed and counted.
+    fn try_next(&mut self) -> Result<Option<Self::Item>, IterManError> {
+        match self.read_raw()? {
+            Some((record, lines, bytes_read)) => {
+                self.incr(lines, bytes_read);
+                record_to_item(record, self.delimiter, self.trim).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Reads ahead one line and caches it so the following `iter` returns it. The
+    /// `line_index` and `bytes_offset` are left untouched until the line is consumed.
+    fn peek(&mut self) -> Option<Self::Item> {
+        let peeked = self.read_raw().ok().flatten()?;
+        let line = record_to_item(peeked.0.clone(), self.delimiter, self.trim).ok();
+        self.peeked = Some(peeked);
+        line
+    }
+}
+
+impl<R: Read> Iterator for ForwardBufferList<R> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter()
+    }
+}
+
+/// How a [MemoryArrayList] picks the sub-list each item is read from. `round_robin` is separate
+/// and decides whether a sub-list starts again once it runs out.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InterleaveStrategy {
+    /// One item from each sub-list in turn, so `[[1, 2], [3, 4]]` yields 1, 3, 2, 4.
+    #[default]
+    RoundRobin,
+    /// Every item of a sub-list before moving on to the next, so `[[1, 2], [3, 4]]` yields
+    /// 1, 2, 3, 4.
+    Sequential,
+    /// Sub-lists picked in proportion to their weight by smooth weighted round robin, so
+    /// `[[1, 2, 3, 4], [5, 6]]` weighted 2 and 1 yields 1, 5, 2, 3, 6, 4. Sub-lists with a weight
+    /// of 0 or without a weight are never read.
+    Weighted(Vec<u32>),
+    /// A random sub-list with items left for every read, drawn from `seed` so the same seed
+    /// always gives the same order. `[[1, 2], [3, 4]]` yields 1 before 2 and 3 before 4, with
+    /// the two sub-lists mixed at random.
+    #[cfg(feature = "shuffle")]
+    Random { seed: u64 },
+}
+
+/// A [MemoryArrayList] is a [ListLike] that reads from a [Vec] of [Vec]s.
+pub struct MemoryArrayList<T: Clone> {
+    lists: Arc<Mutex<Vec<Vec<T>>>>,
+    round_robin: bool,
+    strategy: InterleaveStrategy,
+    /// Used by [InterleaveStrategy::Weighted] to spread out the picks.
+    current_weights: Arc<Mutex<Vec<i64>>>,
+    /// Used by [InterleaveStrategy::Random] to draw the picks.
+    #[cfg(feature = "shuffle")]
+    picker: Option<Mutex<Picker>>,
+    cur_list_index: AtomicUsize,
+    line_indexes: Arc<Mutex<Vec<usize>>>,
+    /// Which sub-lists have been fully read. Only ever set when `round_robin` is off, the
+    /// sub-list is empty or it has made `max_cycles` passes.
+    finished: Arc<Mutex<Vec<bool>>>,
+    /// How many full passes each sub-list makes before it stops. [None] repeats forever.
+    max_cycles: Option<usize>,
+    /// Number of times each sub-list has wrapped back to its start.
+    cycles: Arc<Mutex<Vec<usize>>>,
+}
+
+/// Shows the cursor and the length of every sub-list, but not the items themselves.
+impl<T: Clone> fmt::Debug for MemoryArrayList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lens = lock(&self.lists)
+            .iter()
+            .map(Vec::len)
+            .collect::<Vec<usize>>();
+        f.debug_struct("MemoryArrayList")
+            .field("round_robin", &self.round_robin)
+            .field("cur_list_index", &self.cur_list_index())
+            .field("line_indexes", &self.line_indexes())
+            .field("lens", &lens)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Clone> MemoryArrayList<T> {
+    /// Creates a new [MemoryArrayList] with `round_robin` turned off.
+    /// # Examples
+    /// ```no-run
+    /// let mem_arr = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+    /// let list = MemoryArrayList::new(mem_arr);
+    /// assert_eq!(
+    ///   list.collect::<Vec<i32>>(),
+    ///  [1, 4, 7, 2, 5, 8, 3, 6, 9]
+    /// );
+    /// ```
+    pub fn new(mem_arr: Vec<Vec<T>>) -> Self {
+        Self::with_strategy(mem_arr, InterleaveStrategy::RoundRobin)
+    }
+
+    /// Creates a new [MemoryArrayList] with `round_robin` turned off that picks sub-lists by
+    /// `strategy`.
+    /// # Examples
+    /// ```no-run
+    /// let mem_arr = vec![vec![1, 2], vec![3, 4]];
+    /// let list = MemoryArrayList::with_strategy(mem_arr, InterleaveStrategy::Sequential);
+    /// assert_eq!(list.collect::<Vec<i32>>(), [1, 2, 3, 4]);
+    /// ```
+    pub fn with_strategy(mem_arr: Vec<Vec<T>>, strategy: InterleaveStrategy) -> Self {
+        let len = mem_arr.len();
+        let strategy = match strategy {
+            InterleaveStrategy::Weighted(mut weights) => {
+                weights.resize(len, 0);
+                InterleaveStrategy::Weighted(weights)
+            }
+            strategy => strategy,
+        };
+        Self {
+            lists: Arc::new(Mutex::new(mem_arr)),
+            round_robin: false,
+            #[cfg(feature = "shuffle")]
+            picker: match strategy {
+                InterleaveStrategy::Random { seed } => Some(Mutex::new(Picker::new(seed))),
+                _ => None,
+            },
+            strategy,
+            current_weights: Arc::new(Mutex::new(vec![0; len])),
+            cur_list_index: AtomicUsize::new(0),
+            line_indexes: Arc::new(Mutex::new(vec![0; len])),
+            finished: Arc::new(Mutex::new(vec![false; len])),
+            max_cycles: None,
+            cycles: Arc::new(Mutex::new(vec![0; len])),
+        }
+    }
+
+    /// Creates a new [MemoryArrayList] with `round_robin` turned on.
+    ///
+    /// Each sub-list cycles independently: a sub-list that runs out starts again from its first
+    /// item on its next turn, so shorter sub-lists repeat sooner than longer ones. Empty sub-lists
+    /// are skipped.
+    /// # Examples
+    /// ```no-run
+    /// let mem_arr = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+    /// let list = MemoryArrayList::new_round_robin(mem_arr);
+    /// assert_eq!(
+    ///    list.take(15).collect::<Vec<i32>>(),
+    ///   [1, 4, 7, 2, 5, 8, 3, 6, 9, 1, 4, 7, 2, 5, 8]
+    /// );
+    ///
+    /// let list = MemoryArrayList::new_round_robin(vec![vec![1, 2], vec![3, 4, 5]]);
+    /// assert_eq!(
+    ///    list.take(8).collect::<Vec<i32>>(),
+    ///   [1, 3, 2, 4, 1, 5, 2, 3]
+    /// );
+    /// ```
+    pub fn new_round_robin(mem_arr: Vec<Vec<T>>) -> Self {
+        Self {
+            round_robin: true,
+            ..Self::new(mem_arr)
+        }
+    }
+
+    /// Like [MemoryArrayList::new], but treats a `mem_arr` without a single item as a mistake.
+    /// # Errors
+    /// Returns [IterManError::EmptyList] if every sub-list is empty, or there are none.
+    pub fn try_new(mem_arr: Vec<Vec<T>>) -> Result<Self, IterManError> {
+        if mem_arr.iter().all(Vec::is_empty) {
+            return Err(IterManError::EmptyList);
+        }
+        Ok(Self::new(mem_arr))
+    }
+
+    /// Creates a new [MemoryArrayList] with `round_robin` turned on where each sub-list stops
+    /// after `times` full passes of its own, so every item is yielded `times` times. A `times` of
+    /// 0 yields nothing and 1 behaves like [MemoryArrayList::new].
+    /// # Examples
+    /// ```no-run
+    /// let list = MemoryArrayList::new_round_robin_times(vec![vec![1, 2], vec![3]], 2);
+    /// assert_eq!(list.collect::<Vec<i32>>(), [1, 3, 2, 3, 1, 2]);
+    /// ```
+    pub fn new_round_robin_times(mem_arr: Vec<Vec<T>>, times: usize) -> Self {
+        Self {
+            max_cycles: Some(times),
+            ..Self::new_round_robin(mem_arr)
+        }
+    }
+
+    /// Creates a new [MemoryArrayList] that reads each sub-list in full before moving on to the
+    /// next, as if they were concatenated.
+    /// # Examples
+    /// ```no-run
+    /// let mem_arr = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+    /// let list = MemoryArrayList::new_sequential(mem_arr);
+    /// assert_eq!(
+    ///     list.collect::<Vec<i32>>(),
+    ///     [1, 2, 3, 4, 5, 6, 7, 8, 9]
+    /// );
+    /// ```
+    pub fn new_sequential(mem_arr: Vec<Vec<T>>) -> Self {
+        Self::with_strategy(mem_arr, InterleaveStrategy::Sequential)
+    }
+
+    /// Creates a new sequential [MemoryArrayList] that starts again from the first sub-list after
+    /// the last one is read.
+    /// # Examples
+    /// ```no-run
+    /// let list = MemoryArrayList::new_sequential_round_robin(vec![vec![1, 2], vec![3]]);
+    /// assert_eq!(list.take(5).collect::<Vec<i32>>(), [1, 2, 3, 1, 2]);
+    /// ```
+    pub fn new_sequential_round_robin(mem_arr: Vec<Vec<T>>) -> Self {
+        Self {
+            round_robin: true,
+            ..Self::new_sequential(mem_arr)
+        }
+    }
+
+    /// Creates a new [MemoryArrayList] that picks sub-lists in proportion to their weights. The
+    /// order is a deterministic smooth weighted round robin, so a 2:1 weighting yields A, B, A and
+    /// repeats. Sub-lists with a weight of 0 are never read.
+    /// # Examples
+    /// ```no-run
+    /// let list = MemoryArrayList::new_weighted(vec![(vec![1, 2, 3, 4], 2), (vec![5, 6], 1)]);
+    /// assert_eq!(list.collect::<Vec<i32>>(), [1, 5, 2, 3, 6, 4]);
+    /// ```
+    pub fn new_weighted(weighted: Vec<(Vec<T>, u32)>) -> Self {
+        let (mem_arr, weights): (Vec<Vec<T>>, Vec<u32>) = weighted.into_iter().unzip();
+        Self::with_strategy(mem_arr, InterleaveStrategy::Weighted(weights))
+    }
+
+    /// Creates a new weighted [MemoryArrayList] where each sub-list cycles independently, so the
+    /// weighting holds for as long as the list is read.
+    /// # Examples
+    /// ```no-run
+    /// let list = MemoryArrayList::new_weighted_round_robin(vec![(vec![1], 2), (vec![2], 1)]);
+    /// assert_eq!(list.take(6).collect::<Vec<i32>>(), [1, 2, 1, 1, 2, 1]);
+    /// ```
+    pub fn new_weighted_round_robin(weighted: Vec<(Vec<T>, u32)>) -> Self {
+        Self {
+            round_robin: true,
+            ..Self::new_weighted(weighted)
+        }
+    }
+
+    /// Picks the next sub-list by smooth weighted round robin, updating `current_weights`.
+    /// Sub-lists with nothing left to read are marked as finished and skipped.
+    fn weighted_pick(
+        weights: &[u32],
+        lists: &[Vec<T>],
+        line_indexes: &[usize],
+        finished: &mut [bool],
+        current_weights: &mut [i64],
+    ) -> Option<usize> {
+        loop {
+            let candidates = (0..lists.len())
+                .filter(|&i| !finished[i] && weights[i] > 0)
+                .collect::<Vec<usize>>();
+            if candidates.is_empty() {
+                return None;
+            }
+
+            let total = candidates
+                .iter()
+                .map(|&i| i64::from(weights[i]))
+                .sum::<i64>();
+            for &i in &candidates {
+                current_weights[i] += i64::from(weights[i]);
+            }
+
+            // Ties go to the first sub-list.
+            let picked = candidates
+                .into_iter()
+                .max_by_key(|&i| (current_weights[i], std::cmp::Reverse(i)))?;
+            current_weights[picked] -= total;
+
+            if line_indexes[picked] < lists[picked].len() {
+                return Some(picked);
+            }
+            finished[picked] = true;
+        }
+    }
+
+    /// Finds the sub-list and line the next sequential read comes from, starting at
+    /// `cur_list_index`. The returned flag is set when the position wrapped back to the start.
+    fn sequential_position(
+        &self,
+        lists: &[Vec<T>],
+        line_indexes: &[usize],
+    ) -> Option<(usize, usize, bool)> {
+        let cur_list_index = self.cur_list_index.load(Ordering::Relaxed);
+        if let Some(i) = (cur_list_index..lists.len()).find(|&i| line_indexes[i] < lists[i].len()) {
+            return Some((i, line_indexes[i], false));
+        }
+
+        if !self.round_robin {
+            return None;
+        }
+
+        let i = lists.iter().position(|list| !list.is_empty())?;
+        Some((i, 0, true))
+    }
+
+    /// Moves sub-list `list_index` past the item just read, wrapping it to the start when
+    /// `round_robin` is on and it has passes left.
+    fn advance(&self, list_index: usize, len: usize, line_indexes: &mut [usize]) {
+        line_indexes[list_index] += 1;
+        if !self.round_robin || line_indexes[list_index] < len {
+            return;
+        }
+
+        let mut cycles = lock(&self.cycles);
+        if self
+            .max_cycles
+            .map_or(true, |max_cycles| cycles[list_index] + 1 < max_cycles)
+        {
+            cycles[list_index] += 1;
+            line_indexes[list_index] = 0;
+        }
+    }
+
+    /// Reset every sub-list back to its first item and start again from the first sub-list.
+    pub fn reset(&mut self) {
+        self.cur_list_index.store(0, Ordering::Relaxed);
+        lock(&self.line_indexes).fill(0);
+        lock(&self.finished).fill(false);
+        lock(&self.current_weights).fill(0);
+        lock(&self.cycles).fill(0);
+    }
+
+    /// Turn `round_robin` on or off without rebuilding the list. Turning it on wraps every
+    /// sub-list that has been read to the end back to its start, so iteration carries on.
+    pub fn set_round_robin(&mut self, round_robin: bool) {
+        self.round_robin = round_robin;
+        if !round_robin {
+            return;
+        }
+
+        let lists = lock(&self.lists);
+        let mut line_indexes = lock(&self.line_indexes);
+        let mut finished = lock(&self.finished);
+        let mut cycles = lock(&self.cycles);
+        for (i, list) in lists.iter().enumerate() {
+            // Sequential reads wrap every sub-list at once when the last one runs out.
+            if self.strategy == InterleaveStrategy::Sequential
+                || list.is_empty()
+                || line_indexes[i] < list.len()
+            {
+                continue;
+            }
+            if self
+                .max_cycles
+                .map_or(true, |max_cycles| cycles[i] + 1 < max_cycles)
+            {
+                cycles[i] += 1;
+                line_indexes[i] = 0;
+                finished[i] = false;
+            }
+        }
+    }
+
+    /// Index of the sub-list the next read starts looking from.
+    pub fn cur_list_index(&self) -> usize {
+        self.cur_list_index.load(Ordering::Relaxed)
+    }
+
+    /// A snapshot of the `line_index` of every sub-list.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryArrayList::new(vec![vec![1, 2], vec![3, 4]]);
+    /// list.next();
+    /// assert_eq!(list.line_indexes(), [1, 0]);
+    /// ```
+    pub fn line_indexes(&self) -> Vec<usize> {
+        lock(&self.line_indexes).clone()
+    }
+
+    /// Restore the position of every sub-list along with `cur_list_index`, as read from
+    /// [MemoryArrayList::line_indexes] and [MemoryArrayList::cur_list_index]. Cycle counts aren't
+    /// part of the position and are left as they are.
+    /// # Examples
+    /// ```no-run
+    /// let mut list = MemoryArrayList::new(vec![vec![1, 2], vec![3, 4]]);
+    /// list.seek_all(&[1, 1], 0).unwrap();
+    /// assert_eq!(list.collect::<Vec<i32>>(), [2, 4]);
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::LengthMismatch] if `per_list` doesn't have one entry per sub-list and
+    /// [IterManError::MemoryOutOfBounds] if an index is past the end of its sub-list or
+    /// `cur_list_index` is past the last sub-list. Nothing is moved on error.
+    pub fn seek_all(
+        &mut self,
+        per_list: &[usize],
+        cur_list_index: usize,
+    ) -> Result<(), IterManError> {
+        let lists = lock(&self.lists);
+        validate_per_list(
+            &lists.iter().map(Vec::len).collect::<Vec<usize>>(),
+            per_list,
+        )?;
+        if cur_list_index > lists.len() {
+            return Err(IterManError::MemoryOutOfBounds {
+                line_index: cur_list_index,
+                max_len: lists.len(),
+            });
+        }
+
+        lock(&self.line_indexes).copy_from_slice(per_list);
+        lock(&self.finished).fill(false);
+        self.cur_list_index.store(cur_list_index, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+impl<T: Clone> Iterator for MemoryArrayList<T>
+where
+    T: Clone,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        MemoryArrayList::iter(self)
+    }
+}
+
+impl<T: Clone> ListLike for MemoryArrayList<T> {
+    type Item = T;
+
+    /// Reads the next item from the sub-list picked by the [InterleaveStrategy], skipping
+    /// sub-lists that have been fully read. Iteration stops once every sub-list is finished.
+    fn iter(&mut self) -> Option<Self::Item> {
+        if self.max_cycles == Some(0) {
+            return None;
+        }
+
+        let lists = lock(&self.lists);
+        let mut line_indexes = lock(&self.line_indexes);
+        let mut finished = lock(&self.finished);
+
+        let list_index = match &self.strategy {
+            InterleaveStrategy::Sequential => {
+                let (list_index, line_index, wrapped) =
+                    self.sequential_position(&lists, &line_indexes)?;
+                if wrapped {
+                    line_indexes.fill(0);
+                }
+
+                line_indexes[list_index] = line_index + 1;
+                self.cur_list_index.store(list_index, Ordering::SeqCst);
+                return Some(lists[list_index][line_index].clone());
+            }
+            InterleaveStrategy::Weighted(weights) => {
+                let mut current_weights = lock(&self.current_weights);
+                Self::weighted_pick(
+                    weights,
+                    &lists,
+                    &line_indexes,
+                    &mut finished,
+                    &mut current_weights,
+                )?
+            }
+            #[cfg(feature = "shuffle")]
+            InterleaveStrategy::Random { .. } => {
+                let picker = self.picker.as_ref()?;
+                lock(picker).pick(&available(&lists, &line_indexes))?
+            }
+            InterleaveStrategy::RoundRobin => {
+                for _ in 0..lists.len() {
+                    let mut cur_list_index = self.cur_list_index.load(Ordering::Relaxed);
+                    if cur_list_index >= lists.len() {
+                        cur_list_index = 0;
+                    }
+                    self.cur_list_index
+                        .store(cur_list_index + 1, Ordering::SeqCst);
+
+                    if finished[cur_list_index] {
+                        continue;
+                    }
+
+                    let list = &lists[cur_list_index];
+                    let cur_line_index = line_indexes[cur_list_index];
+                    if cur_line_index >= list.len() {
+                        finished[cur_list_index] = true;
+                        continue;
+                    }
+
+                    self.advance(cur_list_index, list.len(), &mut line_indexes);
+
+                    return Some(list[cur_line_index].clone());
+                }
+
+                return None;
+            }
+        };
+
+        let line_index = line_indexes[list_index];
+        self.advance(list_index, lists[list_index].len(), &mut line_indexes);
+        self.cur_list_index.store(list_index, Ordering::SeqCst);
+        Some(lists[list_index][line_index].clone())
+    }
+
+    fn peek(&mut self) -> Option<Self::Item> {
+        if self.max_cycles == Some(0) {
+            return None;
+        }
+
+        let lists = lock(&self.lists);
+        let line_indexes = lock(&self.line_indexes);
+        let cur_list_index = self.cur_list_index.load(Ordering::Relaxed);
+
+        match &self.strategy {
+            InterleaveStrategy::Sequential => {
+                let (list_index, line_index, _) =
+                    self.sequential_position(&lists, &line_indexes)?;
+                Some(lists[list_index][line_index].clone())
+            }
+            InterleaveStrategy::Weighted(weights) => {
+                let mut finished = lock(&self.finished).clone();
+                let mut current_weights = lock(&self.current_weights).clone();
+                let list_index = Self::weighted_pick(
+                    weights,
+                    &lists,
+                    &line_indexes,
+                    &mut finished,
+                    &mut current_weights,
+                )?;
+                Some(lists[list_index][line_indexes[list_index]].clone())
+            }
+            #[cfg(feature = "shuffle")]
+            InterleaveStrategy::Random { .. } => {
+                // Draw from a copy so the pick is made again, the same way, by `iter`.
+                let mut picker = lock(self.picker.as_ref()?).clone();
+                let list_index = picker.pick(&available(&lists, &line_indexes))?;
+                Some(lists[list_index][line_indexes[list_index]].clone())
+            }
+            InterleaveStrategy::RoundRobin => (0..lists.len())
+                .map(|i| (cur_list_index + i) % lists.len())
+                .find_map(|i| lists[i].get(line_indexes[i]).cloned()),
+        }
+    }
+}
+
+/// The sub-lists that still have an item to read.
+#[cfg(feature = "shuffle")]
+fn available<T>(lists: &[Vec<T>], line_indexes: &[usize]) -> Vec<usize> {
+    (0..lists.len())
+        .filter(|&i| line_indexes[i] < lists[i].len())
+        .collect()
+}
+
+/// A [BufferArrayList] is a [ListLike] that reads from a [Vec] of [BufferList]s.
+pub struct BufferArrayList<T: Read + Seek> {
+    buf_reader: Arc<Mutex<Vec<BufferList<T>>>>,
+    finished: AtomicUsize,
+    round_robin: bool,
+    arr_index: AtomicUsize,
+    line_indexes: Arc<Mutex<Vec<usize>>>,
+    bytes_offset: AtomicUsize,
+}
+
+/// Shows the cursor without touching the readers.
+impl<T: Read + Seek> fmt::Debug for BufferArrayList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferArrayList")
+            .field("round_robin", &self.round_robin)
+            .field("cur_list_index", &self.cur_list_index())
+            .field("line_indexes", &self.line_indexes())
+            .field("bytes_offset", &self.bytes_offset())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Read + Seek> BufferArrayList<T> {
+    /// Creates a new [BufferArrayList] with `round_robin` turned off.
+    /// # Examples
+    /// ```no-run
+    /// let a = BufferList::new(BufReader::new(Cursor::new("1\n2\n3\n")));
+    /// let b = BufferList::new(BufReader::new(Cursor::new("4\n5\n6\n")));
+    /// let list = BufferArrayList::new(vec![a, b]);
+    /// assert_eq!(
+    ///     list.collect::<Vec<String>>(),
+    ///     ["1", "4", "2", "5", "3", "6"]
+    /// );
+    /// ```
+    pub fn new(buf_arr: Vec<BufferList<T>>) -> Self {
+        let buf_len = &buf_arr.len();
+        Self {
+            buf_reader: Arc::new(Mutex::new(buf_arr)),
+            round_robin: false,
+            finished: AtomicUsize::new(0),
+            arr_index: AtomicUsize::new(0),
+            line_indexes: Arc::new(Mutex::new(vec![0; *buf_len])),
+            bytes_offset: AtomicUsize::new(0),
+        }
+    }
+
+    /// Creates a new [BufferArrayList] with `round_robin` turned on for every buffer.
+    /// # Examples
+    /// ```no-run
+    /// let a = BufferList::new(BufReader::new(Cursor::new("1\n2\n")));
+    /// let b = BufferList::new(BufReader::new(Cursor::new("3\n4\n")));
+    /// let list = BufferArrayList::new_round_robin(vec![a, b]);
+    /// assert_eq!(
+    ///     list.take(6).collect::<Vec<String>>(),
+    ///     ["1", "3", "2", "4", "1", "3"]
+    /// );
+    /// ```
+    pub fn new_round_robin(buf_arr: Vec<BufferList<T>>) -> Self {
+        Self {
+            round_robin: true,
+            ..Self::new(buf_arr)
+        }
+    }
+
+    /// Index of the buffer the next read starts looking from.
+    pub fn cur_list_index(&self) -> usize {
+        self.arr_index.load(Ordering::Relaxed)
+    }
+
+    /// A snapshot of the `line_index` of every buffer.
+    pub fn line_indexes(&self) -> Vec<usize> {
+        lock(&self.line_indexes).clone()
+    }
+
+    /// The sum of every buffer's `bytes_offset`. Rewinding a `round_robin` buffer takes its share
+    /// back to zero.
+    pub fn bytes_offset(&self) -> usize {
+        self.bytes_offset.load(Ordering::Relaxed)
+    }
+
+    /// Turn `round_robin` on or off for every buffer without rebuilding the list. Turning it on
+    /// after every buffer has run out makes the next read rewind them.
+    pub fn set_round_robin(&mut self, round_robin: bool) {
+        self.round_robin = round_robin;
+        if round_robin {
+            self.finished.store(0, Ordering::SeqCst);
+        }
+    }
+
+    /// Restore the position of every buffer along with `cur_list_index`, as read from
+    /// [BufferArrayList::line_indexes] and [BufferArrayList::cur_list_index]. Each buffer is moved
+    /// with [BufferList::seek_to_line], so this reads every buffer up to its line, and
+    /// `bytes_offset` becomes the sum of the buffers' offsets.
+    /// # Errors
+    /// Returns [IterManError::LengthMismatch] if `per_list` doesn't have one entry per buffer,
+    /// [IterManError::StreamOutOfBounds] if a buffer has fewer lines than its index and
+    /// [IterManError::MemoryOutOfBounds] if `cur_list_index` is past the last buffer. Every buffer
+    /// is put back where it was on error.
+    pub fn seek_all(
+        &mut self,
+        per_list: &[usize],
+        cur_list_index: usize,
+    ) -> Result<(), IterManError> {
+        let mut bufs = self.buf_reader.lock()?;
+        if per_list.len() != bufs.len() {
+            return Err(length_mismatch(bufs.len(), per_list));
+        }
+        if cur_list_index > bufs.len() {
+            return Err(IterManError::MemoryOutOfBounds {
+                line_index: cur_list_index,
+                max_len: bufs.len(),
+            });
+        }
+
+        let prev = bufs
+            .iter()
+            .map(|buf| (buf.line_index(), buf.bytes_offset()))
+            .collect::<Vec<(usize, usize)>>();
+        for (i, &line_index) in per_list.iter().enumerate() {
+            if let Err(e) = bufs[i].seek_to_line(line_index) {
+                for (buf, &(line_index, bytes_offset)) in bufs.iter_mut().zip(&prev) {
+                    buf.seek(line_index, bytes_offset)?;
+                }
+                return Err(e);
+            }
+        }
+
+        lock(&self.line_indexes).copy_from_slice(per_list);
+        self.bytes_offset.store(
+            bufs.iter().map(BufferList::bytes_offset).sum(),
+            Ordering::SeqCst,
+        );
+        self.finished.store(0, Ordering::SeqCst);
+        self.arr_index.store(cur_list_index, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+impl<T: Read + Seek> Iterator for BufferArrayList<T>
+where
+    T: Read + Seek,
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        BufferArrayList::iter(self)
+    }
+}
+
+impl<T: Read + Seek> ListLike for BufferArrayList<T> {
+    type Item = String;
+
+    /// Reads one line from each buffer in turn. With `round_robin` a buffer at EOF is rewound,
+    /// otherwise it is skipped, and iteration stops once every buffer in a row has reported EOF.
+    fn iter(&mut self) -> Option<Self::Item> {
+        let mut bufs = self.buf_reader.lock().ok()?;
+        let buf_len = bufs.len();
+
+        while self.finished.load(Ordering::Relaxed) < buf_len {
+            let mut arr_index = self.arr_index.load(Ordering::Relaxed);
+            if arr_index >= buf_len {
+                arr_index = 0;
+            }
+            self.arr_index.store(arr_index + 1, Ordering::Relaxed);
+
+            let buf = &mut bufs[arr_index];
+            let prev_offset = buf.bytes_offset();
+            let mut line = buf.iter();
+            if line.is_none() && self.round_robin && buf.seek(0, 0).is_ok() {
+                line = buf.iter();
+            }
+
+            match line {
+                Some(line) => {
+                    // Swap this buffer's share of the total, which shrinks if it was rewound.
+                    self.line_indexes.lock().ok()?[arr_index] = buf.line_index();
+                    self.bytes_offset.fetch_sub(prev_offset, Ordering::SeqCst);
+                    self.bytes_offset
+                        .fetch_add(buf.bytes_offset(), Ordering::SeqCst);
+                    self.finished.store(0, Ordering::Relaxed);
+                    return Some(line);
+                }
+                None => {
+                    self.finished.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Peeks the next buffer in rotation that has a line available.
+    fn peek(&mut self) -> Option<Self::Item> {
+        let mut bufs = self.buf_reader.lock().ok()?;
+        let buf_len = bufs.len();
+        let arr_index = self.arr_index.load(Ordering::Relaxed);
+
+        for i in 0..buf_len {
+            let buf = &mut bufs[(arr_index + i) % buf_len];
+            let mut line = buf.peek();
+            if line.is_none() && self.round_robin && buf.seek(0, 0).is_ok() {
+                line = buf.peek();
+            }
+
+            if line.is_some() {
+                return line;
+            }
+        }
+
+        None
+    }
+}
+
+/// Check that `per_list` has one index per sub-list and that none is past the end of its sub-list.
+fn validate_per_list(lens: &[usize], per_list: &[usize]) -> Result<(), IterManError> {
+    if per_list.len() != lens.len() {
+        return Err(length_mismatch(lens.len(), per_list));
+    }
+
+    match lens
+        .iter()
+        .zip(per_list)
+        .find(|(len, line_index)| line_index > len)
+    {
+        Some((&max_len, &line_index)) => Err(IterManError::MemoryOutOfBounds {
+            line_index,
+            max_len,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// The error for a `per_list` slice that doesn't have one entry for each of the `expected` lists.
+fn length_mismatch(expected: usize, per_list: &[usize]) -> IterManError {
+    IterManError::LengthMismatch {
+        expected,
+        found: per_list.len(),
+    }
+}
+
+/// Lock `mutex`, recovering the guard if another thread panicked while holding it. The in-memory
+/// lists only keep data and indexes behind their locks, and those are never left half updated,
+/// so they stay usable. A [BufferList] reader is different: a panic mid-read can leave it out of
+/// step with the counters, so its lock is never recovered and reads report
+/// [IterManError::Poisoned] or [None] instead.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Write a cursor state file as whitespace separated numbers. The file is written next to `path`
+/// first and then renamed over it, so a crash mid-write never leaves a truncated state behind.
+pub(crate) fn write_state(path: &Path, state: &[usize]) -> Result<(), IterManError> {
+    let contents = state
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>()
+        .join(" ");
+    write_atomic(path, contents + "\n")
+}
+
+/// Write `contents` to a sibling of `path` named with `.tmp` appended, then rename it over
+/// `path`. Appending rather than replacing the extension keeps `state.json` and `state.txt`
+/// from sharing a temp file, and a `state.tmp` target from being written in place.
+pub(crate) fn write_atomic(path: &Path, contents: String) -> Result<(), IterManError> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+    std::fs::write(&tmp_path, contents)?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Read a cursor state file written by [write_state], expecting exactly `len` numbers.
+pub(crate) fn read_state(path: &Path, len: usize) -> Result<Vec<usize>, IterManError> {
+    let contents = std::fs::read_to_string(path)?;
+    let state = contents
+        .split_whitespace()
+        .map(|n| n.parse::<usize>())
+        .collect::<Result<Vec<usize>, _>>()
+        .map_err(|e| IterManError::Io(format!("invalid state file {}: {e}", path.display())))?;
+
+    if state.len() != len {
+        return Err(IterManError::Io(format!(
+            "invalid state file {}: expected {len} values, found {}",
+            path.display(),
+            state.len()
+        )));
+    }
+
+    Ok(state)
+}
+
+/// The order files are read in by the `mem_list_from_dir` functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrderBy {
+    /// Ascending by path.
+    #[default]
+    Name,
+    /// Descending by path.
+    NameDesc,
+    /// Oldest modification time first, falling back to the path for ties.
+    Modified,
+}
+
+/// Create a [MemoryList] from a directory by reading each file into memory, ordered by path.
+/// # Examples
+/// ```no-run
+/// let list = mem_list_from_dir("src", false).unwrap();
+/// assert_eq!(list.collect::<Vec<String>>().len(), 4);
+/// ```
+/// # Errors
+/// This function will return an error if the path is not a directory.
+pub fn mem_list_from_dir(
+    path: &str,
+    round_robin: bool,
+) -> Result<MemoryList<String>, IterManError> {
+    mem_list_from_files(
+        &dir_files(Path::new(path), false, OrderBy::Name, &mut |_| true)?,
+        round_robin,
+    )
+}
+
+/// Create a [MemoryList] from a directory and all of its subdirectories by reading each file into
+/// memory. Subdirectories are walked depth-first and entries are sorted by path at every level, so
+/// the order is the same across runs. Symlinks are skipped.
+/// # Examples
+/// ```no-run
+/// let list = mem_list_from_dir_recursive("corpus", false).unwrap();
+/// ```
+/// # Errors
+/// This function will return an error if the path or any subdirectory can't be read.
+pub fn mem_list_from_dir_recursive(
+    path: &str,
+    round_robin: bool,
+) -> Result<MemoryList<String>, IterManError> {
+    mem_list_from_files(
+        &dir_files(Path::new(path), true, OrderBy::Name, &mut |_| true)?,
+        round_robin,
+    )
+}
+
+/// Create a [MemoryList] from the files in a directory that match `filter`. Files that don't match
+/// are never read, so large unwanted files don't end up in memory. Ordering and symlink handling
+/// are the same as [mem_list_from_dir_recursive].
+/// # Examples
+/// ```no-run
+/// let list = mem_list_from_dir_filtered("corpus", false, true, |path| {
+///     path.extension().is_some_and(|ext| ext == "txt")
+/// })
+/// .unwrap();
+/// ```
+/// # Errors
+/// This function will return an error if the path or any subdirectory can't be read.
+pub fn mem_list_from_dir_filtered(
+    path: &str,
+    round_robin: bool,
+    recursive: bool,
+    mut filter: impl FnMut(&Path) -> bool,
+) -> Result<MemoryList<String>, IterManError> {
+    mem_list_from_files(
+        &dir_files(Path::new(path), recursive, OrderBy::Name, &mut filter)?,
+        round_robin,
+    )
+}
+
+/// Create a [MemoryList] from a directory like [mem_list_from_dir], reading the files in the
+/// given `order_by` so the list is the same on every machine.
+/// # Examples
+/// ```no-run
+/// let list = mem_list_from_dir_ordered("corpus", false, OrderBy::Modified).unwrap();
+/// ```
+/// # Errors
+/// This function will return an error if the path or any file can't be read.
+pub fn mem_list_from_dir_ordered(
+    path: &str,
+    round_robin: bool,
+    order_by: OrderBy,
+) -> Result<MemoryList<String>, IterManError> {
+    mem_list_from_files(
+        &dir_files(Path::new(path), false, order_by, &mut |_| true)?,
+        round_robin,
+    )
+}
+
+/// Create a [MemoryList] from a directory like [mem_list_from_dir], pairing each file's contents
+/// with the path it was read from.
+/// # Examples
+/// ```no-run
+/// let list = mem_list_from_dir_with_paths("corpus", false).unwrap();
+/// for (path, contents) in list {
+///     println!("{}: {} bytes", path.display(), contents.len());
+/// }
+/// ```
+/// # Errors
+/// This function will return an error if the path or any file can't be read.
+pub fn mem_list_from_dir_with_paths(
+    path: &str,
+    round_robin: bool,
+) -> Result<MemoryList<(PathBuf, String)>, IterManError> {
+    let mut contents = vec![];
+    for file in dir_files(Path::new(path), false, OrderBy::Name, &mut |_| true)? {
+        let text = String::from_utf8(std::fs::read(&file)?)?;
+        contents.push((file, text));
+    }
+
+    if round_robin {
+        return Ok(MemoryList::new_round_robin(contents));
+    }
+    Ok(MemoryList::new(contents))
+}
+
+/// Collect the regular files in `path` that match `filter`, sorted by `order_by`, descending into
+/// subdirectories when `recursive` is set.
+fn dir_files(
+    path: &Path,
+    recursive: bool,
+    order_by: OrderBy,
+    filter: &mut dyn FnMut(&Path) -> bool,
+) -> Result<Vec<PathBuf>, IterManError> {
+    let mut entries = std::fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<PathBuf>, _>>()?;
+    match order_by {
+        OrderBy::Name => entries.sort(),
+        OrderBy::NameDesc => entries.sort_by(|a, b| b.cmp(a)),
+        OrderBy::Modified => entries.sort_by_cached_key(|path| {
+            let modified = std::fs::symlink_metadata(path).and_then(|meta| meta.modified());
+            (modified.ok(), path.clone())
+        }),
+    }
+
+    let mut files = vec![];
+    for path in entries {
+        if path.is_symlink() {
+            continue;
+        }
+
+        if path.is_file() {
+            if filter(&path) {
+                files.push(path);
+            }
+        } else if recursive && path.is_dir() {
+            files.extend(dir_files(&path, recursive, order_by, filter)?);
+        }
+    }
+
+    Ok(files)
+}
+
+/// Read each file into memory in the order given.
+fn mem_list_from_files(
+    files: &[PathBuf],
+    round_robin: bool,
+) -> Result<MemoryList<String>, IterManError> {
+    let mut contents = vec![];
+    for file in files {
+        contents.push(String::from_utf8(std::fs::read(file)?)?);
+    }
+
+    if round_robin {
+        return Ok(MemoryList::new_round_robin(contents));
+    }
+    Ok(MemoryList::new(contents))
+}
+
+/// Create a [MemoryList] from a string by splitting it into chunks of at most `chunk_by` bytes.
+/// A chunk never ends in the middle of a multi-byte character, so chunks that would split one
+/// are cut short at the previous character boundary. A character wider than `chunk_by` gets a
+/// chunk of its own.
+/// # Examples
+/// ```no-run
+/// let text = "hello world";
+/// let list = mem_list_from_chunks(text, 5, true).unwrap();
+/// assert_eq!(
+///    list.take(6).collect::<Vec<String>>(),
+///   ["hello", " worl", "d", "hello", " worl", "d"]
+/// );
+/// ```
+pub fn mem_list_from_chunks(
+    text: &str,
+    chunk_by: usize,
+    round_robin: bool,
+) -> Result<MemoryList<String>, IterManError> {
+    let mut chunks = vec![];
+    let mut rest = text;
+    while !rest.is_empty() {
+        let mut end = chunk_by.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == 0 {
+            end = rest.chars().next().map_or(rest.len(), char::len_utf8);
+        }
+
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk.to_string());
+        rest = tail;
+    }
+
+    if round_robin {
+        return Ok(MemoryList::new_round_robin(chunks));
+    }
+    Ok(MemoryList::new(chunks))
+}
+
+/// Create a [MemoryList] from a string by splitting it into chunks of `chunk_by` characters
+/// (Unicode scalar values). A `chunk_by` of 0 is treated as 1.
+/// # Examples
+/// ```no-run
+/// let list = mem_list_from_chars("héllo wörld", 5, false).unwrap();
+/// assert_eq!(list.collect::<Vec<String>>(), ["héllo", " wörl", "d"]);
+/// ```
+pub fn mem_list_from_chars(
+    text: &str,
+    chunk_by: usize,
+    round_robin: bool,
+) -> Result<MemoryList<String>, IterManError> {
+    let chars = text.chars().collect::<Vec<char>>();
+    let chunks = chars
+        .chunks(chunk_by.max(1))
+        .map(|chunk| chunk.iter().collect::<String>())
+        .collect::<Vec<String>>();
+
+    if round_robin {
+        return Ok(MemoryList::new_round_robin(chunks));
+    }
+    Ok(MemoryList::new(chunks))
+}
+
+/// Create a [MemoryList] from a string by splitting it into blocks of up to `lines_per_chunk`
+/// lines, joined by `\n`. The last block holds whatever lines are left, and empty text gives an
+/// empty list. A `lines_per_chunk` of 0 is treated as 1.
+/// # Examples
+/// ```no-run
+/// let list = mem_list_from_line_chunks("a\nb\nc", 2, false).unwrap();
+/// assert_eq!(list.collect::<Vec<String>>(), ["a\nb", "c"]);
+/// ```
+pub fn mem_list_from_line_chunks(
+    text: &str,
+    lines_per_chunk: usize,
+    round_robin: bool,
+) -> Result<MemoryList<String>, IterManError> {
+    let lines = text.lines().collect::<Vec<&str>>();
+    let chunks = lines
+        .chunks(lines_per_chunk.max(1))
+        .map(|chunk| chunk.join("\n"))
+        .collect::<Vec<String>>();
+
+    if round_robin {
+        return Ok(MemoryList::new_round_robin(chunks));
+    }
+    Ok(MemoryList::new(chunks))
+}
+
+/// Create a [MemoryList] with one item per word of `text`, splitting on Unicode whitespace. Runs
+/// of whitespace count as a single separator, so there are no empty words.
+/// # Examples
+/// ```no-run
+/// let list = mem_list_from_words("  hello   world ", false).unwrap();
+/// assert_eq!(list.collect::<Vec<String>>(), ["hello", "world"]);
+/// ```
+pub fn mem_list_from_words(
+    text: &str,
+    round_robin: bool,
+) -> Result<MemoryList<String>, IterManError> {
+    let words = text
+        .split_whitespace()
+        .map(str::to_string)
+        .collect::<Vec<String>>();
+
+    if round_robin {
+        return Ok(MemoryList::new_round_robin(words));
+    }
+    Ok(MemoryList::new(words))
+}
+
+/// Create a [MemoryList] from a string by splitting it into chunks of `chunk_by` extended
+/// grapheme clusters, so a character and its combining marks always stay together. A `chunk_by`
+/// of 0 is treated as 1.
+/// # Examples
+/// ```no-run
+/// let list = mem_list_from_graphemes("e\u{301}a", 1, false).unwrap();
+/// assert_eq!(list.collect::<Vec<String>>(), ["e\u{301}", "a"]);
+/// ```
+#[cfg(feature = "graphemes")]
+pub fn mem_list_from_graphemes(
+    text: &str,
+    chunk_by: usize,
+    round_robin: bool,
+) -> Result<MemoryList<String>, IterManError> {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let graphemes = text.graphemes(true).collect::<Vec<&str>>();
+    let chunks = graphemes
+        .chunks(chunk_by.max(1))
+        .map(|chunk| chunk.concat())
+        .collect::<Vec<String>>();
+
+    if round_robin {
+        return Ok(MemoryList::new_round_robin(chunks));
+    }
+    Ok(MemoryList::new(chunks))
+}
+
+/// Create a [MemoryList] from a string by splitting it on every match of the regex `pattern`,
+/// with the matched text dropped. Lookaround is supported, so a pattern like `\n(?=\d{4}-)`
+/// splits a log into records without losing the date that starts each one. Text before the
+/// first match and after the last is kept even if it's empty.
+/// # Examples
+/// ```no-run
+/// let list = mem_list_from_regex_split("a1b22c", r"\d+", false).unwrap();
+/// assert_eq!(list.collect::<Vec<String>>(), ["a", "b", "c"]);
+/// ```
+/// # Errors
+/// Returns [IterManError::Regex] if `pattern` isn't a valid regex or matching gives up, such as
+/// when it hits the backtracking limit.
+#[cfg(feature = "regex")]
+pub fn mem_list_from_regex_split(
+    text: &str,
+    pattern: &str,
+    round_robin: bool,
+) -> Result<MemoryList<String>, IterManError> {
+    let regex = fancy_regex::Regex::new(pattern)?;
+
+    let mut segments = vec![];
+    let mut start = 0;
+    for found in regex.find_iter(text) {
+        let found = found?;
+        segments.push(text[start..found.start()].to_string());
+        start = found.end();
+    }
+    segments.push(text[start..].to_string());
+
+    if round_robin {
+        return Ok(MemoryList::new_round_robin(segments));
+    }
+    Ok(MemoryList::new(segments))
+}
+
+/// Take items from `a` and `b` in turn, starting with `a`, even when they're different kinds of
+/// list such as a [MemoryList] and a [BufferList] of [String]s. Once one runs out the other
+/// carries on alone, unless [Interleave::with_stop_at_shortest] is set.
+/// # Examples
+/// ```no-run
+/// let a = MemoryList::new(vec!["1".to_string(), "3".to_string()]);
+/// let b = BufferList::new(BufReader::new(Cursor::new("2\n4\n")));
+/// assert_eq!(interleave(a, b).collect::<Vec<String>>(), ["1", "2", "3", "4"]);
+/// ```
+pub fn interleave<A: ListLike, B: ListLike<Item = A::Item>>(a: A, b: B) -> Interleave<A, B> {
+    Interleave::new(a, b)
+}
+
+/// Merge `lists` that are each already sorted by `compare` into one sorted stream, the merge step
+/// of an external sort. Only the current line of each list is held in memory, in a binary heap.
+/// `round_robin` is turned off on every list since the inputs have to end. Lines that compare
+/// equal come out in the order of the lists they were read from.
+/// # Examples
+/// ```no-run
+/// let a = BufferList::new(BufReader::new(Cursor::new("1\n4\n7\n")));
+/// let b = BufferList::new(BufReader::new(Cursor::new("2\n3\n9\n")));
+/// let merged = merge_sorted(vec![a, b], |a, b| a.cmp(b));
+/// assert_eq!(merged.collect::<Vec<String>>(), ["1", "2", "3", "4", "7", "9"]);
+/// ```
+pub fn merge_sorted<T: Read + Seek>(
+    mut lists: Vec<BufferList<T>>,
+    compare: impl Fn(&String, &String) -> std::cmp::Ordering,
+) -> impl Iterator<Item = String> {
+    let compare = std::rc::Rc::new(compare);
+    let mut heads = std::collections::BinaryHeap::with_capacity(lists.len());
+    for (list_index, list) in lists.iter_mut().enumerate() {
+        list.set_round_robin(false);
+        if let Some(line) = list.next() {
+            heads.push(MergeHead {
+                line,
+                list_index,
+                compare: compare.clone(),
+            });
+        }
+    }
+
+    std::iter::from_fn(move || {
+        let head = heads.pop()?;
+        if let Some(line) = lists[head.list_index].next() {
+            heads.push(MergeHead {
+                line,
+                list_index: head.list_index,
+                compare: compare.clone(),
+            });
+        }
+        Some(head.line)
+    })
+}
+
+/// The current line of one of the lists being merged by [merge_sorted]. It's ordered in reverse
+/// so the [std::collections::BinaryHeap] pops the smallest line first.
+struct MergeHead<F> {
+    line: String,
+    list_index: usize,
+    compare: std::rc::Rc<F>,
+}
+
+impl<F: Fn(&String, &String) -> std::cmp::Ordering> Ord for MergeHead<F> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.compare)(&other.line, &self.line).then(other.list_index.cmp(&self.list_index))
+    }
+}
+
+impl<F: Fn(&String, &String) -> std::cmp::Ordering> PartialOrd for MergeHead<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: Fn(&String, &String) -> std::cmp::Ordering> PartialEq for MergeHead<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl<F: Fn(&String, &String) -> std::cmp::Ordering> Eq for MergeHead<F> {}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// A reader that counts how often it's seeked, to check a read doesn't rescan the stream.
+    struct SeekCounter {
+        inner: Cursor<Vec<u8>>,
+        seeks: Arc<AtomicUsize>,
+    }
+
+    impl Read for SeekCounter {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.inner.read(buf)
+        }
+    }
+
+    impl Seek for SeekCounter {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.seeks.fetch_add(1, Ordering::Relaxed);
+            self.inner.seek(pos)
+        }
+    }
+
+    /// A reader that can't seek, for checking seek errors are passed on.
+    struct FailingSeek(Cursor<Vec<u8>>);
+
+    impl Read for FailingSeek {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+
+    impl Seek for FailingSeek {
+        fn seek(&mut self, _pos: SeekFrom) -> std::io::Result<u64> {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "seek failed",
+            ))
+        }
+    }
+
+    #[test]
+    fn it_should_create_buffer_array_list() {
+        let reader = mock_buffer_reader();
+        let buf_reader = BufferList::new(reader);
+        let list = BufferArrayList::new(vec![buf_reader]);
+        assert_eq!(list.collect::<Vec<String>>(), ["1", "2", "3"]);
+    }
+
+    #[test]
+    fn it_should_interleave_buffer_array_lists() {
+        let a = BufferList::new(mock_buffer_reader());
+        let b = BufferList::new(BufReader::new(Cursor::new("4\n5\n")));
+        let mut list = BufferArrayList::new(vec![a, b]);
+        assert_eq!(list.next(), Some("1".to_string()));
+        assert_eq!(list.next(), Some("4".to_string()));
+        assert_eq!(list.arr_index.load(Ordering::Relaxed), 2);
+        assert_eq!(*list.line_indexes.lock().unwrap(), [1, 1]);
+        assert_eq!(list.bytes_offset.load(Ordering::Relaxed), 4);
+        assert_eq!(list.collect::<Vec<String>>(), ["2", "5", "3"]);
+    }
+
+    #[test]
+    fn it_should_create_buffer_array_lists_with_round_robin() {
+        let a = BufferList::new(mock_buffer_reader());
+        let b = BufferList::new(BufReader::new(Cursor::new("4\n5\n")));
+        let mut list = BufferArrayList::new_round_robin(vec![a, b]);
+        assert_eq!(
+            list.by_ref().take(8).collect::<Vec<String>>(),
+            ["1", "4", "2", "5", "3", "4", "1", "5"]
+        );
+        assert_eq!(list.line_indexes(), [1, 2]);
+        assert_eq!(list.bytes_offset(), 6);
+    }
+
+    #[test]
+    fn buffer_array_list_should_return_nothing_when_empty() {
+        let a = BufferList::new_round_robin(BufReader::new(Cursor::new("")));
+        let list = BufferArrayList::new_round_robin(vec![a]);
+        assert_eq!(list.take(5).collect::<Vec<String>>().len(), 0);
+    }
+
+    #[test]
+    fn array_lists_should_expose_their_positions() {
+        let mut list = MemoryArrayList::new(vec![vec![1, 2, 3], vec![4, 5], vec![6]]);
+        list.next();
+        list.next();
+        list.next();
+        list.next();
+        assert_eq!(list.line_indexes(), [2, 1, 1]);
+        assert_eq!(list.cur_list_index(), 1);
+
+        let a = BufferList::new(BufReader::new(Cursor::new("1\n2\n")));
+        let b = BufferList::new(BufReader::new(Cursor::new("33\n")));
+        let mut list = BufferArrayList::new(vec![a, b]);
+        list.next();
+        list.next();
+        list.next();
+        assert_eq!(list.line_indexes(), [2, 1]);
+        assert_eq!(list.cur_list_index(), 1);
+        assert_eq!(list.bytes_offset(), 7);
+    }
+
+    #[test]
+    fn array_lists_should_restore_their_positions() {
+        let lists = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8]];
+        let mut list = MemoryArrayList::new(lists.clone());
+        list.next();
+        list.next();
+        list.next();
+        list.next();
+        let (line_indexes, cur_list_index) = (list.line_indexes(), list.cur_list_index());
+        let rest = list.collect::<Vec<i32>>();
+
+        let mut list = MemoryArrayList::new(lists);
+        list.seek_all(&line_indexes, cur_list_index).unwrap();
+        assert_eq!(list.collect::<Vec<i32>>(), rest);
+
+        let mut list = MemoryArrayList::new(vec![vec![1, 2], vec![3]]);
+        assert_eq!(
+            list.seek_all(&[1, 2], 0),
+            Err(IterManError::MemoryOutOfBounds {
+                line_index: 2,
+                max_len: 1
+            })
+        );
+        assert_eq!(
+            list.seek_all(&[1], 0),
+            Err(IterManError::LengthMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(list.line_indexes(), [0, 0]);
+
+        let a = BufferList::new(BufReader::new(Cursor::new("1\n2\n3\n")));
+        let b = BufferList::new(BufReader::new(Cursor::new("4\n5\n")));
+        let mut list = BufferArrayList::new(vec![a, b]);
+        assert!(list.seek_all(&[1, 3], 0).is_err());
+        assert_eq!(list.next(), Some("1".to_string()));
+        list.seek_all(&[2, 1], 1).unwrap();
+        assert_eq!(list.bytes_offset(), 6);
+        assert_eq!(list.collect::<Vec<String>>(), ["5", "3"]);
+    }
+
+    #[test]
+    fn it_should_create_memory_array_lists() {
+        let mem_arr = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let list = MemoryArrayList::new(mem_arr);
+        assert_eq!(
+            list.take(15).collect::<Vec<i32>>(),
+            [1, 4, 7, 2, 5, 8, 3, 6, 9]
+        );
+    }
+
+    #[test]
+    fn memory_array_list_new_should_not_clone_items() {
+        static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug, PartialEq)]
+        struct Counted(i32);
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                CLONES.fetch_add(1, Ordering::SeqCst);
+                Counted(self.0)
+            }
+        }
+
+        let mut list = MemoryArrayList::new(vec![vec![Counted(1)], vec![Counted(2)]]);
+        assert_eq!(CLONES.load(Ordering::SeqCst), 0);
+        assert_eq!(list.next(), Some(Counted(1)));
+        assert_eq!(CLONES.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn memory_array_list_should_finish_uneven_lists() {
+        let list = MemoryArrayList::new(vec![vec![1, 2, 3], vec![4]]);
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 4, 2, 3]);
+
+        let list = MemoryArrayList::new(vec![vec![1], vec![], vec![2, 3], vec![4]]);
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 2, 4, 3]);
+
+        let mut list = MemoryArrayList::new(vec![vec![1], vec![2, 3]]);
+        assert_eq!(list.next(), Some(1));
+        assert_eq!(list.next(), Some(2));
+        assert_eq!(list.peek(), Some(3));
+        assert_eq!(list.next(), Some(3));
+        assert_eq!(list.next(), None);
+    }
+
+    #[test]
+    fn memory_array_list_should_cycle_uneven_lists_independently() {
+        let list = MemoryArrayList::new_round_robin(vec![vec![1, 2], vec![3, 4, 5]]);
+        assert_eq!(
+            list.take(12).collect::<Vec<i32>>(),
+            [1, 3, 2, 4, 1, 5, 2, 3, 1, 4, 2, 5]
+        );
+
+        let list = MemoryArrayList::new_round_robin(vec![vec![1], vec![2, 3, 4], vec![]]);
+        assert_eq!(
+            list.take(9).collect::<Vec<i32>>(),
+            [1, 2, 1, 3, 1, 4, 1, 2, 1]
+        );
+    }
+
+    #[test]
+    fn memory_array_list_should_read_sequentially() {
+        let mem_arr = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let list = MemoryArrayList::new_sequential(mem_arr);
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        let mut list = MemoryArrayList::new_sequential(vec![vec![], vec![1], vec![], vec![2]]);
+        assert_eq!(list.peek(), Some(1));
+        assert_eq!(list.by_ref().collect::<Vec<i32>>(), [1, 2]);
+        assert_eq!(list.peek(), None);
+    }
+
+    #[test]
+    fn memory_array_list_should_read_sequentially_with_round_robin() {
+        let list = MemoryArrayList::new_sequential_round_robin(vec![vec![1, 2], vec![], vec![3]]);
+        assert_eq!(list.take(7).collect::<Vec<i32>>(), [1, 2, 3, 1, 2, 3, 1]);
+
+        let list: MemoryArrayList<i32> =
+            MemoryArrayList::new_sequential_round_robin(vec![vec![], vec![]]);
+        assert_eq!(list.take(3).count(), 0);
+    }
+
+    #[test]
+    fn memory_array_list_should_pick_by_weight() {
+        let list = MemoryArrayList::new_weighted_round_robin(vec![(vec!["a"], 2), (vec!["b"], 1)]);
+        let draws = list.take(300).collect::<Vec<&str>>();
+        assert_eq!(draws[..6], ["a", "b", "a", "a", "b", "a"]);
+        assert_eq!(draws.iter().filter(|&&d| d == "a").count(), 200);
+        assert_eq!(draws.iter().filter(|&&d| d == "b").count(), 100);
+    }
+
+    #[test]
+    fn memory_array_list_weighted_should_finish_every_list() {
+        let mut list =
+            MemoryArrayList::new_weighted(vec![(vec![1, 2, 3, 4], 2), (vec![5], 1), (vec![6], 0)]);
+        assert_eq!(list.peek(), Some(1));
+        assert_eq!(list.next(), Some(1));
+        assert_eq!(list.peek(), Some(5));
+        assert_eq!(list.collect::<Vec<i32>>(), [5, 2, 3, 4]);
+    }
+
+    #[test]
+    fn memory_array_list_should_return_nothing_when_empty() {
+        let list: MemoryArrayList<i32> = MemoryArrayList::new_round_robin(vec![]);
+        assert_eq!(list.take(3).count(), 0);
+
+        let list: MemoryArrayList<i32> = MemoryArrayList::new_round_robin(vec![vec![], vec![]]);
+        assert_eq!(list.take(3).count(), 0);
+    }
+
+    #[test]
+    fn it_should_create_memory_array_lists_with_round_robin() {
+        let mem_arr = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let list = MemoryArrayList::new_round_robin(mem_arr);
+        assert_eq!(
+            list.take(15).collect::<Vec<i32>>(),
+            [1, 4, 7, 2, 5, 8, 3, 6, 9, 1, 4, 7, 2, 5, 8]
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn should_from_dir() {
+        let dir = mem_list_from_dir("src", false).unwrap();
+        assert_eq!(dir.collect::<Vec<String>>().len(), 4);
+        // buffer iterator
+        let reader = BufReader::new(Cursor::new("1\n2\n3\n"));
+        let list = BufferList::new(reader); // will reach EOF and stop
+        assert_eq!(list.collect::<Vec<String>>(), ["1", "2", "3"]);
+    }
+
+    #[test]
+    fn it_should_create_a_mem_list_from_dir_recursively() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("b/c")).unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(dir.path().join("b/b.txt"), "b").unwrap();
+        std::fs::write(dir.path().join("b/c/c.txt"), "c").unwrap();
+        std::fs::write(dir.path().join("d.txt"), "d").unwrap();
+
+        let path = dir.path().to_str().unwrap();
+        let list = mem_list_from_dir_recursive(path, false).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["a", "b", "c", "d"]);
+
+        let list = mem_list_from_dir(path, false).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["a", "d"]);
+    }
+
+    #[test]
+    fn it_should_create_a_mem_list_from_dir_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = std::time::SystemTime::now();
+        for (name, age) in [("a.txt", 1), ("b.txt", 3), ("c.txt", 2)] {
+            let path = dir.path().join(name);
+            std::fs::write(&path, &name[..1]).unwrap();
+            let modified = now - std::time::Duration::from_secs(age * 60);
+            filetime::set_file_mtime(&path, filetime::FileTime::from_system_time(modified))
+                .unwrap();
+        }
+
+        let path = dir.path().to_str().unwrap();
+        let list = mem_list_from_dir_ordered(path, false, OrderBy::Name).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["a", "b", "c"]);
+
+        let list = mem_list_from_dir_ordered(path, false, OrderBy::NameDesc).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["c", "b", "a"]);
+
+        let list = mem_list_from_dir_ordered(path, false, OrderBy::Modified).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["b", "c", "a"]);
+    }
+
+    #[test]
+    fn it_should_create_a_mem_list_from_dir_with_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        std::fs::write(dir.path().join("b.txt"), "b").unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+
+        let list = mem_list_from_dir_with_paths(dir.path().to_str().unwrap(), false).unwrap();
+        assert_eq!(
+            list.collect::<Vec<(PathBuf, String)>>(),
+            [
+                (dir.path().join("a.txt"), "a".to_string()),
+                (dir.path().join("b.txt"), "b".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_create_a_mem_list_from_dir_filtered_by_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(dir.path().join("b.log"), "b").unwrap();
+        std::fs::write(dir.path().join("nested/c.txt"), "c").unwrap();
+        std::fs::write(dir.path().join("nested/d.log"), "d").unwrap();
+
+        let path = dir.path().to_str().unwrap();
+        let is_txt = |path: &Path| path.extension().is_some_and(|ext| ext == "txt");
+        let list = mem_list_from_dir_filtered(path, false, true, is_txt).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["a", "c"]);
+
+        let list = mem_list_from_dir_filtered(path, false, false, is_txt).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["a"]);
+    }
+
+    #[test]
+    fn it_should_create_a_mem_list_by_chunks() {
+        let text = "hello world";
+        let list = mem_list_from_chunks(text, 5, true).unwrap();
+        assert_eq!(
+            list.take(6).collect::<Vec<String>>(),
+            ["hello", " worl", "d", "hello", " worl", "d"]
+        );
+    }
+
+    #[test]
+    fn it_should_chunk_on_char_boundaries() {
+        let list = mem_list_from_chunks("héllo wörld", 5, false).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["héll", "o wö", "rld"]);
+
+        let list = mem_list_from_chunks("a😀b", 2, false).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["a", "😀", "b"]);
+    }
+
+    #[test]
+    fn it_should_chunk_by_lines() {
+        let text = "1\n2\n3\n4\n5\n6\n7\n";
+        let list = mem_list_from_line_chunks(text, 3, false).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["1\n2\n3", "4\n5\n6", "7"]);
+
+        let list = mem_list_from_line_chunks("", 3, false).unwrap();
+        assert_eq!(list.remaining(), 0);
+
+        let list = mem_list_from_line_chunks("a\nb", 0, true).unwrap();
+        assert_eq!(list.take(3).collect::<Vec<String>>(), ["a", "b", "a"]);
+    }
+
+    #[test]
+    fn it_should_split_into_words() {
+        let list = mem_list_from_words("  hello   world ", false).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["hello", "world"]);
+
+        let list = mem_list_from_words("a\tb\u{3000}c\n", true).unwrap();
+        assert_eq!(list.take(4).collect::<Vec<String>>(), ["a", "b", "c", "a"]);
+        assert_eq!(mem_list_from_words(" \n ", false).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn it_should_chunk_by_chars() {
+        let list = mem_list_from_chars("héllo wörld", 5, false).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["héllo", " wörl", "d"]);
+
+        let list = mem_list_from_chars("日本語のテキスト", 3, true).unwrap();
+        assert_eq!(
+            list.take(4).collect::<Vec<String>>(),
+            ["日本語", "のテキ", "スト", "日本語"]
+        );
+
+        // Without grapheme segmentation a combining mark is its own character.
+        let list = mem_list_from_chars("e\u{301}a", 1, false).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["e", "\u{301}", "a"]);
+    }
+
+    #[test]
+    #[cfg(feature = "graphemes")]
+    fn it_should_chunk_by_graphemes() {
+        let list = mem_list_from_graphemes("e\u{301}a\u{308}o", 2, false).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["e\u{301}a\u{308}", "o"]);
+
+        let list = mem_list_from_graphemes("日本語", 2, false).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["日本", "語"]);
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn it_should_split_on_a_regex() {
+        let log = "2024-01-01 start\n  detail\n2024-01-02 stop\n2024-01-03 again";
+        let list = mem_list_from_regex_split(log, r"\n(?=\d{4}-)", false).unwrap();
+        assert_eq!(
+            list.collect::<Vec<String>>(),
+            [
+                "2024-01-01 start\n  detail",
+                "2024-01-02 stop",
+                "2024-01-03 again"
+            ]
+        );
+
+        let e = mem_list_from_regex_split(log, "(unclosed", false).err();
+        assert!(matches!(e, Some(IterManError::Regex(_))));
+    }
+
+    #[test]
+    fn mem_list_from_dir_should_error_on_missing_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing");
+        let e = mem_list_from_dir(path.to_str().unwrap(), false).err();
+        assert!(matches!(e, Some(IterManError::Io(_))));
+    }
+
+    #[test]
+    fn memory_list_reaches_end_correctly_as_i32() {
+        let list = MemoryList::new(vec![2, 3, 4]);
+        let collected: Vec<i32> = list.collect();
+        assert_eq!(collected, [2, 3, 4]);
+    }
+
+    #[test]
+    fn memory_list_reaches_end_correctly_as_str() {
+        let list = MemoryList::new(vec!["2", "3", "4"]);
+        let collected: Vec<&str> = list.collect();
+        assert_eq!(collected, ["2", "3", "4"]);
+    }
+
+    #[test]
+    fn memory_list_round_robins_correctly() {
+        let list = MemoryList::new_round_robin(vec![2, 3, 4]);
+        let collected: Vec<i32> = list.take(6).collect();
+        assert_eq!(collected, [2, 3, 4, 2, 3, 4]);
+    }
+
+    #[test]
+    fn memory_list_should_return_nothing_when_empty() {
+        let list = MemoryList::new_round_robin(vec![]);
+        let collected: Vec<i32> = list.take(10).collect();
+        assert_eq!(collected, Vec::<i32>::new());
+    }
+
+    #[test]
+    fn memory_list_should_report_remaining() {
+        let mut list = MemoryList::new(vec![2, 3, 4, 5]);
+        assert_eq!(list.remaining(), 4);
+        list.next();
+        list.next();
+        assert_eq!(list.remaining(), 2);
+        assert_eq!(list.size_hint(), (2, Some(2)));
+        list.by_ref().for_each(drop);
+        assert_eq!(list.remaining(), 0);
+        assert_eq!(list.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn memory_list_round_robin_size_hint_should_be_unbounded() {
+        let list = MemoryList::new_round_robin(vec![2, 3, 4]);
+        assert_eq!(list.size_hint(), (usize::MAX, None));
+        assert_eq!(list.remaining(), 3);
+
+        let list: MemoryList<i32> = MemoryList::new_round_robin(vec![]);
+        assert_eq!(list.size_hint(), (0, Some(0)));
+
+        // Adapters see the unbounded hint rather than the length of the current pass.
+        let list = MemoryList::new_round_robin(vec![1, 2, 3]).skip(1);
+        assert_eq!(list.size_hint(), (usize::MAX - 1, None));
+        assert_eq!(list.take(5).collect::<Vec<i32>>(), [2, 3, 1, 2, 3]);
+    }
+
+    #[test]
+    fn memory_list_should_iterate_from_both_ends() {
+        let mut list = MemoryList::new(vec![1, 2, 3, 4, 5]);
+        assert_eq!(list.next(), Some(1));
+        assert_eq!(list.next_back(), Some(5));
+        assert_eq!(list.next(), Some(2));
+        assert_eq!(list.next_back(), Some(4));
+        assert_eq!(list.remaining(), 1);
+        assert_eq!(list.peek(), Some(3));
+        assert_eq!(list.next_back(), Some(3));
+        assert_eq!(list.next(), None);
+        assert_eq!(list.next_back(), None);
+        assert_eq!(list.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn memory_list_should_reverse() {
+        let list = MemoryList::new(vec![1, 2, 3]);
+        assert_eq!(list.rev().collect::<Vec<i32>>(), [3, 2, 1]);
+    }
+
+    #[test]
+    fn memory_list_next_back_should_be_unsupported_with_round_robin() {
+        let mut list = MemoryList::new_round_robin(vec![1, 2, 3]);
+        assert_eq!(list.next_back(), None);
+        assert_eq!(list.take(4).collect::<Vec<i32>>(), [1, 2, 3, 1]);
+    }
+
+    #[test]
+    fn memory_list_should_yield_each_item_once_across_threads() {
+        // Clones share the Vec and cursor, so the threads race on the same line_index.
+        let list = MemoryList::new((0..10_000).collect());
+        let handles = (0..8)
+            .map(|_| {
+                let list = list.clone();
+                std::thread::spawn(move || list.collect::<Vec<i32>>())
+            })
+            .collect::<Vec<_>>();
+
+        let mut collected = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect::<Vec<i32>>();
+        collected.sort();
+        assert_eq!(collected, (0..10_000).collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn buffer_list_reaches_end_correctly() {
+        let reader = mock_buffer_reader();
+        let list = BufferList::new(reader);
+
+        let collected: Vec<String> = list.collect();
+        assert_eq!(collected, ["1", "2", "3"]);
+    }
+
+    #[test]
+    fn buffer_list_should_open_a_file_by_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lines.txt");
+        std::fs::write(&path, "1\n2\n3\n").unwrap();
+
+        let list = BufferList::from_path(&path).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["1", "2", "3"]);
+
+        let list = BufferList::from_path_round_robin(&path).unwrap();
+        assert_eq!(list.take(4).collect::<Vec<String>>(), ["1", "2", "3", "1"]);
+
+        let e = BufferList::from_path(dir.path().join("missing.txt")).err();
+        assert!(matches!(e, Some(IterManError::Io(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn buffer_list_should_read_gzip() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(b"1\n2\n3\n").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut list = BufferList::from_gzip(Cursor::new(compressed.clone())).unwrap();
+        assert_eq!(list.next(), Some("1".to_string()));
+        list.seek(2, 4).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["3"]);
+
+        let list = BufferList::from_gzip_round_robin(Cursor::new(compressed)).unwrap();
+        assert_eq!(list.take(4).collect::<Vec<String>>(), ["1", "2", "3", "1"]);
+
+        let e = BufferList::from_gzip(Cursor::new(b"not gzip".to_vec())).err();
+        assert!(matches!(e, Some(IterManError::Io(_))));
+    }
+
+    #[test]
+    fn buffer_list_should_preserve_whitespace_without_trim() {
+        let reader = BufReader::new(Cursor::new("  spaced  \n\tx\r\n"));
+        let mut list = BufferList::new(reader).with_trim(false);
+        assert_eq!(list.next(), Some("  spaced  ".to_string()));
+        assert_eq!(list.bytes_offset(), 11);
+        assert_eq!(list.next(), Some("\tx\r".to_string()));
+        assert_eq!(list.bytes_offset(), 15);
+        assert_eq!(list.next(), None);
+    }
+
+    #[test]
+    fn buffer_list_should_trim_by_trim_mode() {
+        let cases = [
+            (TrimMode::None, ["  a \r", "  b "]),
+            (TrimMode::LineEnding, ["  a ", "  b "]),
+            (TrimMode::Full, ["a", "b"]),
+        ];
+        for (trim_mode, expected) in cases {
+            for input in ["  a \r\n  b \n", "  a \r\n  b "] {
+                let reader = BufReader::new(Cursor::new(input));
+                let list = BufferList::new(reader).with_trim_mode(trim_mode);
+                assert_eq!(list.collect::<Vec<String>>(), expected, "{trim_mode:?}");
+            }
+
+            let reader = BufReader::new(Cursor::new("  a \r\n"));
+            let mut list = BufferList::builder(reader)
+                .trim_mode(trim_mode)
+                .build()
+                .unwrap();
+            let mut buf = String::from(">");
+            list.read_into(&mut buf);
+            assert_eq!(buf, format!(">{}", expected[0]));
+        }
+    }
+
+    #[test]
+    fn buffer_list_should_yield_invalid_utf8_as_bytes() {
+        let text = b"ok\n\xff\xfe\r\nlast".to_vec();
+        let mut list = BufferList::new(BufReader::new(Cursor::new(text.clone())));
+        list.next();
+        assert!(list.try_next().is_err());
+
+        let seeks = Arc::new(AtomicUsize::new(0));
+        let reader = BufReader::new(SeekCounter {
+            inner: Cursor::new(text),
+            seeks: seeks.clone(),
+        });
+        let mut list = BufferList::new(reader);
+        assert_eq!(list.peek(), Some("ok".to_string()));
+        assert_eq!(
+            list.bytes().collect::<Vec<Vec<u8>>>(),
+            [b"ok".to_vec(), b"\xff\xfe\r".to_vec(), b"last".to_vec()]
+        );
+        assert_eq!(list.line_index(), 3);
+        assert_eq!(list.bytes_offset(), 11);
+        assert_eq!(seeks.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn buffer_list_should_read_fixed_width_records() {
+        let text = "abcdefghij";
+        let mut list = BufferList::new(BufReader::new(Cursor::new(text)));
+        let records = list.fixed_records(3).collect::<Vec<Vec<u8>>>();
+        assert_eq!(
+            records,
+            [
+                b"abc".to_vec(),
+                b"def".to_vec(),
+                b"ghi".to_vec(),
+                b"j".to_vec()
+            ]
+        );
+        assert_eq!(list.line_index(), 4);
+        assert_eq!(list.bytes_offset(), 10);
+
+        let mut list = BufferList::new(BufReader::new(Cursor::new(text)));
+        list.seek(1, 3).unwrap();
+        let records = list.try_fixed_records(3).collect::<Vec<_>>();
+        assert_eq!(
+            records,
+            [
+                Ok(b"def".to_vec()),
+                Ok(b"ghi".to_vec()),
+                Err(IterManError::PartialRecord {
+                    width: 3,
+                    record: b"j".to_vec()
+                })
+            ]
+        );
+        assert_eq!(list.fixed_records(0).count(), 0);
+
+        let mut list = BufferList::new(BufReader::new(Cursor::new(text)));
+        assert_eq!(list.peek(), Some(text.to_string()));
+        assert_eq!(list.fixed_records(4).next(), Some(b"abcd".to_vec()));
+
+        let reader = BufReader::new(FailingSeek(Cursor::new(text.as_bytes().to_vec())));
+        let mut list = BufferList::new(reader);
+        list.peek();
+        let records = list.try_fixed_records(3).collect::<Vec<_>>();
+        assert_eq!(
+            records,
+            [Err(IterManError::StreamOutOfBounds {
+                line_index: 0,
+                bytes_offset: 0,
+                max_len: 0
+            })]
+        );
+    }
+
+    #[test]
+    fn buffer_list_should_collect_into_a_preallocated_vec() {
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.next();
+        let lines = list.collect_preallocated();
+        assert_eq!(lines, ["2", "3"]);
+        assert!(lines.capacity() >= 2);
+        assert!(list.collect_preallocated().is_empty());
+    }
+
+    #[test]
+    fn buffer_list_should_read_lines_longer_than_its_capacity() {
+        let long = "x".repeat(10_000);
+        let reader = Cursor::new(format!("{long}\nshort\n{long}"));
+        let mut list = BufferList::with_capacity(reader, 16);
+        assert_eq!(list.next(), Some(long.clone()));
+        assert_eq!(list.next(), Some("short".to_string()));
+        assert_eq!(list.bytes_offset(), 10_007);
+        assert_eq!(list.next(), Some(long));
+        assert_eq!(list.next(), None);
+
+        list.seek(1, 10_001).unwrap();
+        assert_eq!(list.next(), Some("short".to_string()));
+    }
+
+    #[test]
+    fn lists_should_drain_the_remaining_items() {
+        let mut list = MemoryList::new(vec![1, 2, 3, 4, 5]);
+        list.next();
+        list.next();
+        assert_eq!(list.drain_remaining(), [3, 4, 5]);
+        assert_eq!(list.line_index(), 5);
+        assert!(list.drain_remaining().is_empty());
+
+        let seeks = Arc::new(AtomicUsize::new(0));
+        let reader = BufReader::new(SeekCounter {
+            inner: Cursor::new(b"1\n2\n3\n4\n5\n".to_vec()),
+            seeks: Arc::clone(&seeks),
+        });
+        let mut list = BufferList::new_round_robin(reader);
+        list.next();
+        list.next();
+        // Only the remaining lines are read, without going back to count the whole stream.
+        assert_eq!(list.drain_remaining(), ["3", "4", "5"]);
+        assert_eq!(seeks.load(Ordering::Relaxed), 0);
+        assert_eq!(list.line_index(), 5);
+        assert_eq!(list.next(), Some("1".to_string()));
+
+        let reader = BufReader::new(Cursor::new("1\n2\n3\n4\n5\n"));
+        let mut list = BufferList::new(reader);
+        list.build_line_index().unwrap();
+        list.next();
+        list.next();
+        let lines = list.drain_remaining();
+        assert_eq!(lines, ["3", "4", "5"]);
+        assert!(lines.capacity() >= 3);
+    }
+
+    #[test]
+    fn buffer_list_should_skip_blank_lines() {
+        let reader = BufReader::new(Cursor::new("a\n\n\nb\n"));
+        let mut list = BufferList::builder(reader)
+            .skip_blank(true)
+            .build()
+            .unwrap();
+        assert_eq!(list.next(), Some("a".to_string()));
+        assert_eq!(list.next(), Some("b".to_string()));
+        assert_eq!((list.line_index(), list.bytes_offset()), (4, 6));
+        assert_eq!(list.next(), None);
+
+        list.seek(1, 2).unwrap();
+        assert_eq!(list.peek(), Some("b".to_string()));
+        assert_eq!(list.next(), Some("b".to_string()));
+
+        let reader = BufReader::new(Cursor::new("\n \r\n"));
+        let mut list = BufferList::new_round_robin(reader).with_skip_blank(true);
+        assert_eq!(list.next(), None);
+    }
+
+    #[test]
+    fn buffer_list_should_skip_comment_lines() {
+        let text = "# settings\nname=iterman\n\n  # indented\nmode=fast # inline\n// other\n";
+        let list = BufferList::new(BufReader::new(Cursor::new(text))).with_trim(false);
+        assert_eq!(
+            list.skip_comments("#").collect::<Vec<String>>(),
+            ["name=iterman", "", "mode=fast # inline", "// other"]
+        );
+
+        let list = BufferList::new(BufReader::new(Cursor::new(text))).with_skip_blank(true);
+        assert_eq!(
+            list.skip_comments("//").collect::<Vec<String>>(),
+            [
+                "# settings",
+                "name=iterman",
+                "# indented",
+                "mode=fast # inline"
+            ]
+        );
+    }
+
+    #[test]
+    fn buffer_list_should_strip_a_leading_bom() {
+        let reader = BufReader::new(Cursor::new(b"\xEF\xBB\xBFfirst\nsecond\n".to_vec()));
+        let mut list = BufferList::new_round_robin(reader);
+        assert_eq!(list.next(), Some("first".to_string()));
+        assert_eq!(list.bytes_offset(), 9);
+        assert_eq!(list.next(), Some("second".to_string()));
+        assert_eq!(list.next(), Some("first".to_string()));
+
+        list.seek(0, 0).unwrap();
+        assert_eq!(list.lines_between(0, 1).unwrap(), ["first"]);
+        assert_eq!(list.next(), Some("first".to_string()));
+
+        let reader = BufReader::new(Cursor::new(b"\xEF\xBB\xBFfirst\n".to_vec()));
+        let mut list = BufferList::new(reader).with_strip_bom(false);
+        assert_eq!(list.next(), Some("\u{feff}first".to_string()));
+    }
+
+    #[test]
+    fn buffer_list_should_split_on_a_custom_delimiter() {
+        let reader = BufReader::new(Cursor::new("a b\0 c\0d"));
+        let mut list = BufferList::new(reader).with_delimiter(b'\0', false);
+        assert_eq!(list.next(), Some("a b".to_string()));
+        assert_eq!(list.bytes_offset(), 4);
+        assert_eq!(list.collect::<Vec<String>>(), [" c", "d"]);
+
+        let reader = BufReader::new(Cursor::new("a b\0 c\0d"));
+        let mut list = BufferList::new(reader).with_delimiter(b'\0', true);
+        assert_eq!(list.seek_to_line(1).unwrap(), 4);
+        assert_eq!(list.collect::<Vec<String>>(), ["c", "d"]);
+    }
+
+    #[test]
+    #[cfg(feature = "shuffle")]
+    fn buffer_list_should_sample_distinct_lines_by_seed() {
+        let text = (0..100).map(|n| format!("{n}\n")).collect::<String>();
+
+        let mut list = BufferList::new(BufReader::new(Cursor::new(text.clone())));
+        let a = list.sample(3, 7);
+        assert_eq!(list.line_index(), 100);
+        assert_eq!(a.len(), 3);
+        assert!(a[0] != a[1] && a[1] != a[2] && a[0] != a[2]);
+
+        let mut list = BufferList::new_round_robin(BufReader::new(Cursor::new(text.clone())));
+        assert_eq!(list.sample(3, 7), a);
+
+        let mut list = BufferList::new(BufReader::new(Cursor::new(text)));
+        assert_ne!(list.sample(3, 8), a);
+
+        let mut list = BufferList::new(mock_buffer_reader());
+        assert_eq!(list.peek(), Some("1".to_string()));
+        assert_eq!(list.sample(5, 7).len(), 3);
+
+        let reader = BufReader::new(Cursor::new("\u{feff}a\n\n  \nb\n\n"));
+        let mut list = BufferList::new_round_robin(reader).with_skip_blank(true);
+        let mut sample = list.sample(5, 7);
+        sample.sort();
+        assert_eq!(sample, ["a", "b"]);
+        assert_eq!(list.line_index(), 5);
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "async")]
+    async fn buffer_list_should_stream_lines() {
+        use futures::StreamExt;
+
+        let mut stream = BufferList::new(mock_buffer_reader()).into_stream();
+        assert_eq!(StreamExt::next(&mut stream).await, Some("1".to_string()));
+        assert_eq!(stream.get_ref().line_index(), 1);
+        let rest: Vec<String> = StreamExt::collect(stream).await;
+        assert_eq!(rest, ["2", "3"]);
+
+        let stream = BufferList::new_round_robin(mock_buffer_reader()).into_stream();
+        let lines: Vec<String> = StreamExt::collect(StreamExt::take(stream, 4)).await;
+        assert_eq!(lines, ["1", "2", "3", "1"]);
+    }
+
+    #[test]
+    fn memory_list_clones_should_share_a_cursor() {
+        let list = MemoryList::new((0..1000).collect::<Vec<i32>>());
+        let handles = (0..4)
+            .map(|_| {
+                let mut list = list.clone();
+                std::thread::spawn(move || {
+                    let mut taken = vec![];
+                    while let Some(item) = list.iter() {
+                        taken.push(item);
+                    }
+                    taken
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut taken = handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect::<Vec<i32>>();
+        taken.sort();
+        assert_eq!(taken, (0..1000).collect::<Vec<i32>>());
+        assert_eq!(list.line_index(), 1000);
+    }
+
+    #[test]
+    fn buffer_list_should_read_lines_between() {
+        let reader = BufReader::new(Cursor::new("l0\nl1\nl2\nl3\nl4\n"));
+        let mut list = BufferList::new(reader);
+        list.next();
+        assert_eq!(list.peek(), Some("l1".to_string()));
+
+        assert_eq!(list.lines_between(1, 3).unwrap(), ["l1", "l2"]);
+        assert_eq!(list.lines_between(3, 9).unwrap(), ["l3", "l4"]);
+        assert_eq!(list.lines_between(2, 2).unwrap(), Vec::<String>::new());
+        assert_eq!(list.lines_between(7, 9).unwrap(), Vec::<String>::new());
+        assert_eq!(
+            list.lines_between(3, 1),
+            Err(IterManError::InvalidRange { start: 3, end: 1 })
+        );
+
+        assert_eq!(list.position().line_index, 1);
+        assert_eq!(list.next(), Some("l1".to_string()));
+        assert_eq!(list.next(), Some("l2".to_string()));
+    }
+
+    #[test]
+    fn lists_should_reject_empty_input() {
+        assert_eq!(
+            MemoryList::<i32>::try_new(vec![]).err(),
+            Some(IterManError::EmptyList)
+        );
+        let mut list = MemoryList::try_new_round_robin(vec![1]).unwrap();
+        assert_eq!(list.by_ref().take(2).collect::<Vec<i32>>(), [1, 1]);
+
+        let reader = BufReader::new(Cursor::new(""));
+        assert_eq!(
+            BufferList::try_new(reader).err(),
+            Some(IterManError::EmptyList)
+        );
+        let list = BufferList::try_new(mock_buffer_reader()).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["1", "2", "3"]);
+
+        assert_eq!(
+            MemoryArrayList::<i32>::try_new(vec![vec![], vec![]]).err(),
+            Some(IterManError::EmptyList)
+        );
+        let list = MemoryArrayList::try_new(vec![vec![], vec![1]]).unwrap();
+        assert_eq!(list.collect::<Vec<i32>>(), [1]);
+    }
+
+    #[test]
+    fn memory_list_tees_should_have_independent_cursors() {
+        let mut list = MemoryList::new(vec![1, 2, 3, 4]);
+        list.next();
+        let (mut a, mut b) = list.tee();
+        assert_eq!(a.next(), Some(2));
+        assert_eq!(a.next(), Some(3));
+        assert_eq!(b.next(), Some(2));
+        assert_eq!(
+            (a.line_index(), b.line_index(), list.line_index()),
+            (3, 2, 1)
+        );
+
+        a.push(5);
+        assert_eq!(b.collect::<Vec<i32>>(), [3, 4, 5]);
+        assert_eq!(a.collect::<Vec<i32>>(), [4, 5]);
+    }
+
+    #[test]
+    fn merge_sorted_should_merge_sorted_buffer_lists() {
+        let a = BufferList::new_round_robin(BufReader::new(Cursor::new("1\n4\n7\n")));
+        let b = BufferList::new(BufReader::new(Cursor::new("2\n3\n9\n")));
+        let merged = merge_sorted(vec![a, b], |a, b| a.cmp(b));
+        assert_eq!(
+            merged.collect::<Vec<String>>(),
+            ["1", "2", "3", "4", "7", "9"]
+        );
+
+        let a = BufferList::new(BufReader::new(Cursor::new("9 a\n1 a\n")));
+        let b = BufferList::new(BufReader::new(Cursor::new("10 b\n9 b\n")));
+        let c = BufferList::new(BufReader::new(Cursor::new("")));
+        let by_number = |line: &String| line.split(' ').next().unwrap().parse::<i32>().unwrap();
+        let merged = merge_sorted(vec![a, b, c], |a, b| by_number(b).cmp(&by_number(a)));
+        assert_eq!(
+            merged.collect::<Vec<String>>(),
+            ["10 b", "9 a", "9 b", "1 a"]
+        );
+    }
+
+    #[test]
+    fn lists_should_take_while_persisting_the_stopping_point() {
+        let mut list = MemoryList::new(vec![1, 2, 3, 10, 4]);
+        assert_eq!(list.take_while_persist(|n| *n < 5), [1, 2, 3]);
+        assert_eq!(list.next(), Some(10));
+        assert_eq!(list.take_while_persist(|n| *n < 5), [4]);
+
+        let reader = BufReader::new(Cursor::new("# a\n# b\nbody\n# c\n"));
+        let mut list = BufferList::new(reader);
+        assert_eq!(
+            list.take_while_persist(|line| line.starts_with('#')),
+            ["# a", "# b"]
+        );
+        assert_eq!(list.line_index(), 2);
+        assert_eq!(
+            list.take_while_persist(|line| !line.starts_with('#')),
+            ["body"]
+        );
+        assert_eq!(list.next(), Some("# c".to_string()));
+    }
+
+    #[test]
+    fn memory_array_list_should_interleave_by_strategy() {
+        let lists = vec![vec![1, 2, 3], vec![4, 5], vec![6]];
+        let list = MemoryArrayList::with_strategy(lists.clone(), InterleaveStrategy::RoundRobin);
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 4, 6, 2, 5, 3]);
+
+        let list = MemoryArrayList::with_strategy(lists.clone(), InterleaveStrategy::Sequential);
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 2, 3, 4, 5, 6]);
+
+        let mut list =
+            MemoryArrayList::with_strategy(lists, InterleaveStrategy::Weighted(vec![2, 1]));
+        assert_eq!(list.peek(), Some(1));
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 4, 2, 3, 5]);
+    }
+
+    #[test]
+    #[cfg(feature = "shuffle")]
+    fn memory_array_list_should_interleave_at_random() {
+        let lists = vec![vec![1, 2, 3], vec![4, 5], vec![6]];
+        let strategy = InterleaveStrategy::Random { seed: 7 };
+        let mut list = MemoryArrayList::with_strategy(lists.clone(), strategy.clone());
+        let mut items = vec![];
+        while let Some(peeked) = list.peek() {
+            let item = list.next().unwrap();
+            assert_eq!(peeked, item);
+            items.push(item);
+        }
+        assert_eq!(
+            MemoryArrayList::with_strategy(lists, strategy).collect::<Vec<i32>>(),
+            items
+        );
+
+        let position = |n| items.iter().position(|&item| item == n).unwrap();
+        assert!(position(1) < position(2) && position(2) < position(3));
+        assert!(position(4) < position(5));
+        items.sort();
+        assert_eq!(items, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn lists_should_toggle_round_robin() {
+        let mut list = MemoryList::new(vec![1, 2]);
+        assert_eq!(list.by_ref().count(), 2);
+        list.set_round_robin(true);
+        assert_eq!(list.by_ref().take(3).collect::<Vec<i32>>(), [1, 2, 1]);
+        list.set_round_robin(false);
+        assert_eq!(list.by_ref().collect::<Vec<i32>>(), [2]);
+
+        let mut list = BufferList::new(mock_buffer_reader());
+        assert_eq!(list.by_ref().count(), 3);
+        assert_eq!(list.next(), None);
+        list.set_round_robin(true);
+        assert_eq!(list.next(), Some("1".to_string()));
+        assert_eq!(list.line_index(), 1);
+
+        let mut list = MemoryArrayList::new(vec![vec![1, 2], vec![3]]);
+        assert_eq!(list.by_ref().count(), 3);
+        list.set_round_robin(true);
+        assert_eq!(list.by_ref().take(4).collect::<Vec<i32>>(), [3, 1, 3, 2]);
+
+        let mut list = BufferArrayList::new(vec![
+            BufferList::new(mock_buffer_reader()),
+            BufferList::new(BufReader::new(Cursor::new("4\n"))),
+        ]);
+        assert_eq!(list.by_ref().count(), 4);
+        list.set_round_robin(true);
+        assert_eq!(
+            list.by_ref().take(3).collect::<Vec<String>>(),
+            ["4", "1", "4"]
+        );
+
+        let mut list = ForwardBufferList::from_reader(Cursor::new("1\n"));
+        assert_eq!(list.set_round_robin(true), Err(IterManError::NotSeekable));
+        assert_eq!(list.set_round_robin(false), Ok(()));
+    }
+
+    #[test]
+    fn lists_should_report_progress_after_every_item() {
+        let mut positions = vec![];
+        let lines = BufferList::new(mock_buffer_reader())
+            .on_progress(|position| positions.push(position))
+            .collect::<Vec<String>>();
+        assert_eq!(lines, ["1", "2", "3"]);
+        assert_eq!(
+            positions,
+            [
+                Position {
+                    line_index: 1,
+                    bytes_offset: Some(2)
+                },
+                Position {
+                    line_index: 2,
+                    bytes_offset: Some(4)
+                },
+                Position {
+                    line_index: 3,
+                    bytes_offset: Some(6)
+                }
+            ]
+        );
+        assert!(positions
+            .windows(2)
+            .all(|pair| pair[0].line_index < pair[1].line_index));
+
+        let mut line_indexes = vec![];
+        MemoryList::new_round_robin(vec![1, 2])
+            .on_progress(|position| line_indexes.push(position.line_index))
+            .take(5)
+            .for_each(drop);
+        assert_eq!(line_indexes, [1, 2, 1, 2, 1]);
+    }
+
+    #[test]
+    fn lists_should_number_items_from_their_position() {
+        let lines = BufferList::new(mock_buffer_reader())
+            .numbered()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                (1, "1".to_string()),
+                (2, "2".to_string()),
+                (3, "3".to_string())
+            ]
+        );
+
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.seek_to_line(2).unwrap();
+        assert_eq!(list.numbered().collect::<Vec<_>>(), [(3, "3".to_string())]);
+
+        let list = MemoryList::new_round_robin(vec!['a', 'b', 'c']).with_seek_to(1);
+        assert_eq!(
+            list.numbered().take(3).collect::<Vec<_>>(),
+            [(2, 'b'), (3, 'c'), (1, 'a')]
+        );
+    }
+
+    #[test]
+    fn lists_should_stride_over_items() {
+        let mut list = MemoryList::new((0..10).collect());
+        list.stride(2);
+        assert_eq!(list.size_hint(), (5, Some(5)));
+        assert_eq!(list.next(), Some(0));
+        assert_eq!(list.line_index(), 2);
+        assert_eq!(list.by_ref().collect::<Vec<i32>>(), [2, 4, 6, 8]);
+        assert_eq!(list.line_index(), 10);
+
+        let mut list = MemoryList::new_round_robin(vec![0, 1, 2]);
+        list.stride(2);
+        assert_eq!(list.by_ref().take(4).collect::<Vec<i32>>(), [0, 2, 1, 0]);
+
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.stride(2);
+        assert_eq!(list.next(), Some("1".to_string()));
+        assert_eq!((list.line_index(), list.bytes_offset()), (2, 4));
+        assert_eq!(list.try_next(), Ok(Some("3".to_string())));
+        assert_eq!(list.next(), None);
+
+        let mut list = MemoryList::new((0..10).collect());
+        list.stride(3);
+        assert_eq!(list.advance(2), 2);
+        assert_eq!(list.next(), Some(2));
+
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.stride(2);
+        assert_eq!(list.advance(1), Ok(1));
+        assert_eq!(list.next(), Some("2".to_string()));
+    }
+
+    #[test]
+    fn memory_list_should_iterate_in_reverse() {
+        let mut list = MemoryList::new(vec![1, 2, 3]);
+        list.next();
+        assert_eq!(list.iter_rev().collect::<Vec<i32>>(), [3, 2, 1]);
+        assert_eq!(list.next(), Some(2));
+
+        let list = MemoryList::new_round_robin(vec![1, 2, 3]);
+        assert_eq!(
+            list.iter_rev().take(5).collect::<Vec<i32>>(),
+            [3, 2, 1, 3, 2]
+        );
+        let list = MemoryList::new_round_robin_times(vec![1, 2], 2);
+        assert_eq!(list.iter_rev().collect::<Vec<i32>>(), [2, 1, 2, 1]);
+        assert_eq!(
+            MemoryList::<i32>::new_round_robin(vec![])
+                .iter_rev()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn memory_list_should_sort_in_place() {
+        let mut list = MemoryList::new(vec![3, 10, 1, 7]);
+        let other = list.clone();
+        list.next();
+        list.sort_by_key(|n| std::cmp::Reverse(*n));
+        assert_eq!(other.line_index(), 0);
+        assert_eq!(list.by_ref().collect::<Vec<i32>>(), [10, 7, 3, 1]);
+
+        list.sort();
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 3, 7, 10]);
+
+        let mut list = MemoryList::new(vec![(1, 'b'), (0, 'z'), (1, 'a')]);
+        list.sort_by_key(|pair| pair.0);
+        assert_eq!(
+            list.collect::<Vec<(i32, char)>>(),
+            [(0, 'z'), (1, 'b'), (1, 'a')]
+        );
+    }
+
+    #[test]
+    fn lists_should_roll_back_to_a_checkpoint() {
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.next();
+        let cp = list.checkpoint();
+        let read = list.by_ref().take(2).collect::<Vec<String>>();
+        assert_eq!(read, ["2", "3"]);
+        list.rollback(cp).unwrap();
+        assert_eq!(list.position(), cp);
+        assert_eq!(list.by_ref().take(2).collect::<Vec<String>>(), read);
+
+        let mut list = MemoryList::new(vec![1, 2, 3]);
+        let cp = list.checkpoint();
+        let read = list.by_ref().take(2).collect::<Vec<i32>>();
+        list.rollback(cp).unwrap();
+        assert_eq!(list.by_ref().take(2).collect::<Vec<i32>>(), read);
+        assert_eq!(list.next(), Some(3));
+    }
+
+    #[test]
+    fn lists_should_record_seeks_in_the_seek_log() {
+        let at = |line_index, bytes_offset| Position {
+            line_index,
+            bytes_offset,
+        };
+
+        let mut list = BufferList::new(mock_buffer_reader()).with_seek_log();
+        list.next();
+        list.seek_to_line(2).unwrap();
+        assert_eq!(list.lines_between(0, 1).unwrap(), ["1"]);
+        list.reset();
+        assert_eq!(
+            list.seek_history(),
+            [
+                (at(0, Some(0)), at(1, Some(2))),
+                (at(1, Some(2)), at(2, Some(4))),
+                (at(2, Some(4)), at(0, Some(0))),
+            ]
+        );
+
+        let mut list = MemoryList::new(vec![1, 2, 3]).with_seek_log();
+        list.seek(2).unwrap();
+        list.rollback(at(1, None)).unwrap();
+        assert_eq!(
+            list.seek_history(),
+            [(at(0, None), at(2, None)), (at(2, None), at(1, None))]
+        );
+        assert!(MemoryList::new(vec![1]).seek_history().is_empty());
+    }
+
+    #[test]
+    fn buffer_list_line_index_should_match_a_linear_scan() {
+        let text = "alpha\nbeta\n\ngamma\ndelta";
+        let mut linear = BufferList::new(BufReader::new(Cursor::new(text)));
+        let mut indexed = BufferList::new(BufReader::new(Cursor::new(text)));
+        indexed.next();
+        indexed.build_line_index().unwrap();
+        assert_eq!(indexed.line_index(), 1);
+        assert_eq!(indexed.next(), Some("beta".to_string()));
+
+        for line_index in [4, 0, 3, 2, 5, 1] {
+            assert_eq!(
+                indexed.seek_to_line(line_index).unwrap(),
+                linear.seek_to_line(line_index).unwrap()
+            );
+            assert_eq!(indexed.next(), linear.next());
+        }
+
+        indexed.seek(1, 6).unwrap();
+        assert_eq!(
+            indexed.seek_to_line(6),
+            Err(IterManError::StreamOutOfBounds {
+                line_index: 6,
+                bytes_offset: 23,
+                max_len: 23
+            })
+        );
+        assert_eq!(indexed.next(), Some("beta".to_string()));
+    }
+
+    #[test]
+    fn buffer_list_line_index_should_be_dropped_when_the_stream_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clients.txt");
+        std::fs::write(&path, "a\nb\n").unwrap();
+
+        let mut list = BufferList::from_path(&path).unwrap();
+        list.build_line_index().unwrap();
+        std::fs::write(&path, "ccc\nddd\neee\n").unwrap();
+        assert_eq!(list.seek_to_line(2).unwrap(), 8);
+        assert_eq!(list.next(), Some("eee".to_string()));
+    }
+
+    #[test]
+    fn stdin_list_should_read_forward_only() {
+        let mut list = ForwardBufferList::from_reader(Cursor::new("a@b.com\n c@d.com \ne@f.com"));
+        assert_eq!(list.next(), Some("a@b.com".to_string()));
+        assert_eq!(list.peek(), Some("c@d.com".to_string()));
+        assert_eq!(list.line_index(), 1);
+        assert_eq!(list.bytes_offset(), 8);
+        assert_eq!(list.next(), Some("c@d.com".to_string()));
+        assert_eq!(list.bytes_offset(), 18);
+        assert_eq!(list.seek(0, 0), Err(IterManError::NotSeekable));
+        assert_eq!(list.next(), Some("e@f.com".to_string()));
+        assert_eq!(list.next(), None);
+        assert_eq!(list.line_index(), 3);
+    }
+
+    #[test]
+    fn forward_buffer_list_should_read_readers_that_cant_seek() {
+        // A chain of readers is Read but not Seek.
+        let reader = Cursor::new("1\n2\n").chain(Cursor::new("3\n"));
+        let mut list = ForwardBufferList::from_reader(reader);
+        assert_eq!(list.by_ref().collect::<Vec<String>>(), ["1", "2", "3"]);
+        assert_eq!(list.line_index(), 3);
+        assert_eq!(list.bytes_offset(), 6);
+        assert_eq!(list.seek(0, 0), Err(IterManError::NotSeekable));
+    }
+
+    #[test]
+    fn forward_buffer_list_should_read_records_like_a_buffer_list() {
+        let reader = Cursor::new(b"\xEF\xBB\xBF a \0\0  \0\xff\0b\0".to_vec())
+            .chain(Cursor::new(b"\0".to_vec()));
+        let mut list = ForwardBufferList::from_reader(reader)
+            .with_delimiter(b'\0', true)
+            .with_skip_blank(true);
+        assert_eq!(list.try_next(), Ok(Some("a".to_string())));
+        assert_eq!(list.bytes_offset(), 7);
+        assert_eq!(list.peek(), None);
+        assert_eq!((list.line_index(), list.bytes_offset()), (1, 7));
+        assert!(matches!(list.try_next(), Err(IterManError::Utf8(_))));
+        assert_eq!((list.line_index(), list.bytes_offset()), (4, 13));
+        assert_eq!(list.try_next(), Ok(Some("b".to_string())));
+        assert_eq!(list.try_next(), Ok(None));
+        assert_eq!((list.line_index(), list.bytes_offset()), (6, 16));
+
+        let reader = Cursor::new("\u{feff}  a \r\n");
+        let list = ForwardBufferList::from_reader(reader)
+            .with_strip_bom(false)
+            .with_trim_mode(TrimMode::LineEnding);
+        assert_eq!(list.collect::<Vec<String>>(), ["\u{feff}  a "]);
+    }
+
+    #[test]
+    fn buffer_list_builder_should_combine_options() {
+        let reader = BufReader::new(Cursor::new("a\0 b \0c\0"));
+        let list = BufferList::builder(reader)
+            .round_robin(true)
+            .delimiter(b'\0')
+            .trim(false)
+            .seek_to(1, 2)
+            .build()
+            .unwrap();
+        assert_eq!(list.line_index(), 1);
+        assert_eq!(
+            list.take(4).collect::<Vec<String>>(),
+            [" b ", "c", "a", " b "]
+        );
+
+        let reader = BufReader::new(Cursor::new("a\n"));
+        assert_eq!(
+            BufferList::builder(reader).seek_to(1, 10).build().err(),
+            Some(IterManError::StreamOutOfBounds {
+                line_index: 1,
+                bytes_offset: 10,
+                max_len: 2
+            })
+        );
+    }
+
+    #[test]
+    fn lists_should_debug_their_cursor() {
+        let mut list = MemoryList::new_round_robin(vec![1, 2, 3]);
+        list.next();
+        assert_eq!(
+            format!("{list:?}"),
+            "MemoryList { round_robin: true, line_index: 1, len: 3, .. }"
+        );
+
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.next();
+        assert_eq!(
+            format!("{list:?}"),
+            "BufferList { round_robin: false, line_index: 1, bytes_offset: 2, .. }"
+        );
+
+        let mut list = MemoryArrayList::new(vec![vec![1, 2], vec![3]]);
+        list.next();
+        assert_eq!(
+            format!("{list:?}"),
+            "MemoryArrayList { round_robin: false, cur_list_index: 1, line_indexes: [1, 0], lens: [2, 1], .. }"
+        );
+
+        let mut list = BufferArrayList::new(vec![BufferList::new(mock_buffer_reader())]);
+        list.next();
+        assert!(format!("{list:?}").contains("line_indexes: [1], bytes_offset: 2"));
+    }
+
+    #[test]
+    fn memory_list_should_iterate_by_reference() {
+        let mut list = MemoryList::new(vec![1, 2, 3, 4]);
+        list.next();
+
+        let mut seen = vec![];
+        for item in &list {
+            seen.push(item);
+            if item == 3 {
+                break;
+            }
+        }
+        assert_eq!(seen, [2, 3]);
+        assert_eq!(list.line_index(), 3);
+        assert_eq!(list.next(), Some(4));
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn memory_list_par_for_each_should_visit_every_item_once() {
+        let total = AtomicUsize::new(0);
+        let visits = AtomicUsize::new(0);
+        MemoryList::new((1..=1000).collect::<Vec<usize>>()).par_for_each(|n| {
+            total.fetch_add(n, Ordering::Relaxed);
+            visits.fetch_add(1, Ordering::Relaxed);
+        });
+        assert_eq!(total.into_inner(), 500500);
+        assert_eq!(visits.into_inner(), 1000);
+
+        let total = AtomicUsize::new(0);
+        let list = MemoryList::new_round_robin(vec![1, 2, 3, 4]).with_seek_to(2);
+        let shared = list.clone();
+        list.par_for_each(|n| {
+            total.fetch_add(n, Ordering::Relaxed);
+        });
+        assert_eq!(total.into_inner(), 7);
+        assert_eq!(shared.remaining(), 0);
+    }
+
+    #[test]
+    fn memory_list_round_robin_times_should_stop_after_k_passes() {
+        let mut list = MemoryList::new_round_robin_times(vec![1, 2, 3], 2);
+        assert_eq!(list.size_hint(), (6, Some(6)));
+        assert_eq!(list.by_ref().collect::<Vec<i32>>(), [1, 2, 3, 1, 2, 3]);
+        assert_eq!(list.next(), None);
+        assert_eq!(list.peek(), None);
+
+        list.reset();
+        assert_eq!(list.next(), Some(1));
+
+        let mut list = MemoryList::new_round_robin_times(vec![1, 2, 3], 0);
+        assert_eq!(list.peek(), None);
+        assert_eq!(list.next(), None);
+
+        let list = MemoryList::new_round_robin_times(vec![1, 2, 3], 1);
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn buffer_list_round_robin_times_should_stop_after_k_passes() {
+        let list = BufferList::new_round_robin_times(mock_buffer_reader(), 2);
+        assert_eq!(
+            list.collect::<Vec<String>>(),
+            ["1", "2", "3", "1", "2", "3"]
+        );
+
+        let mut list = BufferList::new_round_robin_times(mock_buffer_reader(), 0);
+        assert_eq!(list.next(), None);
+        assert_eq!(list.line_index(), 0);
+
+        let list = BufferList::new_round_robin_times(mock_buffer_reader(), 1);
+        assert_eq!(list.collect::<Vec<String>>(), ["1", "2", "3"]);
+    }
+
+    #[test]
+    fn memory_array_list_round_robin_times_should_stop_after_k_passes() {
+        let list = MemoryArrayList::new_round_robin_times(vec![vec![1, 2, 3], vec![4, 5]], 2);
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 4, 2, 5, 3, 4, 1, 5, 2, 3]);
+
+        let mut list = MemoryArrayList::new_round_robin_times(vec![vec![1, 2, 3]], 0);
+        assert_eq!(list.peek(), None);
+        assert_eq!(list.next(), None);
+
+        let list = MemoryArrayList::new_round_robin_times(vec![vec![1, 2], vec![3]], 1);
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 3, 2]);
+    }
+
+    #[test]
+    fn buffer_list_clones_should_share_a_cursor() {
+        let text = (0..500).map(|i| format!("{i}\n")).collect::<String>();
+        let list = BufferList::new(BufReader::new(Cursor::new(text.clone().into_bytes())));
+        let handles = (0..4)
+            .map(|_| {
+                let mut list = list.clone();
+                std::thread::spawn(move || {
+                    let mut taken = vec![];
+                    while let Some(line) = list.iter() {
+                        taken.push(line.parse::<i32>().unwrap());
+                    }
+                    taken
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut taken = handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect::<Vec<i32>>();
+        taken.sort();
+        assert_eq!(taken, (0..500).collect::<Vec<i32>>());
+        assert_eq!(list.line_index(), 500);
+        assert_eq!(list.bytes_offset(), text.len());
+    }
+
+    #[test]
+    fn buffer_list_clones_should_share_a_peeked_line() {
+        let mut a = BufferList::new(BufReader::new(Cursor::new("aa\nbbb\ncccc\n")));
+        let mut b = a.clone();
+        assert_eq!(a.peek(), Some("aa".to_string()));
+        assert_eq!(b.peek(), Some("aa".to_string()));
+        assert_eq!((b.line_index(), b.bytes_offset()), (0, 0));
+
+        assert_eq!(b.next(), Some("aa".to_string()));
+        assert_eq!((a.line_index(), a.bytes_offset()), (1, 3));
+        assert_eq!(a.next(), Some("bbb".to_string()));
+        assert_eq!(b.peek(), Some("cccc".to_string()));
+        assert_eq!(a.next(), Some("cccc".to_string()));
+        assert_eq!(b.next(), None);
+        assert_eq!((a.line_index(), a.bytes_offset()), (3, 12));
+
+        // A peek that wraps a round robin list is shared the same way.
+        let mut a = BufferList::new_round_robin(mock_buffer_reader()).with_seek_to(3, 6);
+        let mut b = a.clone();
+        assert_eq!(a.peek(), Some("1".to_string()));
+        assert_eq!((b.line_index(), b.bytes_offset()), (3, 6));
+        assert_eq!(b.next(), Some("1".to_string()));
+        assert_eq!(a.next(), Some("2".to_string()));
+        assert_eq!((b.line_index(), b.bytes_offset()), (2, 4));
+    }
+
+    #[test]
+    fn buffer_list_round_robins_correctly() {
+        let reader = mock_buffer_reader();
+        let list = BufferList::new_round_robin(reader);
+
+        let collected: Vec<String> = list.take(6).collect();
+        assert_eq!(collected, ["1", "2", "3", "1", "2", "3"]);
+    }
+
+    #[test]
+    fn buffer_list_should_return_nothing_with_an_empty_buffer() {
+        let reader = BufReader::new(Cursor::new(""));
+        let list = BufferList::new_round_robin(reader);
+
+        let collected: Vec<String> = list.take(10).collect();
+        assert_eq!(collected.len(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "shuffle")]
+    fn memory_list_should_shuffle_by_seed() {
+        let items = (0..20).collect::<Vec<i32>>();
+        let a = MemoryList::new_shuffled(items.clone(), 7).collect::<Vec<i32>>();
+        let b = MemoryList::new_shuffled(items.clone(), 7).collect::<Vec<i32>>();
+        let c = MemoryList::new_shuffled(items.clone(), 8).collect::<Vec<i32>>();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_ne!(a, items);
+
+        let mut sorted = a.clone();
+        sorted.sort();
+        assert_eq!(sorted, items);
+    }
+
+    #[test]
+    #[cfg(feature = "shuffle")]
+    fn memory_list_should_reshuffle_on_round_robin() {
+        let items = (0..20).collect::<Vec<i32>>();
+        let mut list = MemoryList::new_shuffled_round_robin(items.clone(), 7);
+        let first = list.by_ref().take(20).collect::<Vec<i32>>();
+        let next = list.peek();
+        let second = list.by_ref().take(20).collect::<Vec<i32>>();
+        assert_eq!(next, Some(second[0]));
+        assert_ne!(first, second);
+
+        let mut sorted = second.clone();
+        sorted.sort();
+        assert_eq!(sorted, items);
+    }
+
+    #[test]
+    fn memory_list_should_reset() {
+        let mut list = MemoryList::new(vec![2, 3, 4]);
+        assert_eq!(list.next_back(), Some(4));
+        assert_eq!(list.by_ref().collect::<Vec<i32>>(), [2, 3]);
+        list.reset();
+        assert_eq!(list.line_index(), 0);
+        assert_eq!(list.collect::<Vec<i32>>(), [2, 3, 4]);
+    }
+
+    #[test]
+    fn memory_array_list_should_reset() {
+        let mut list = MemoryArrayList::new(vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(list.by_ref().collect::<Vec<i32>>(), [1, 3, 2, 4]);
+        list.reset();
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 3, 2, 4]);
+    }
+
+    #[test]
+    fn memory_list_should_seek() {
+        let mut list = MemoryList::new_round_robin(vec![2, 3, 4]);
+        list.seek(2).expect("TODO: panic message");
+        assert_eq!(list.next(), Some(4));
+        assert_eq!(list.line_index(), 3);
+    }
+
+    #[test]
+    fn memory_list_with_seek_to() {
+        let mut list = MemoryList::new_round_robin(vec![2, 3, 4]).with_seek_to(2);
+        assert_eq!(list.next(), Some(4));
+        assert_eq!(list.line_index(), 3);
+    }
+
+    #[test]
+    fn memory_list_seek_should_return_false_if_out_of_bounds() {
+        let mut list = MemoryList::new(vec![2, 3, 4]);
+        let e = list.seek(6).unwrap_err();
+        assert_eq!(
+            e,
+            IterManError::MemoryOutOfBounds {
+                line_index: 6,
+                max_len: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn buffer_list_should_seek() {
+        let reader = mock_buffer_reader();
+        let mut list = BufferList::new(reader);
+        list.seek(2, 4).expect("TODO: panic message");
+        assert_eq!(list.next(), Some("3".to_string()));
+        assert_eq!(list.line_index(), 3);
+        assert_eq!(list.bytes_offset(), 6);
+    }
+
+    #[test]
+    fn buffer_list_with_seek_to() {
+        let reader = mock_buffer_reader();
+        let mut list = BufferList::new(reader).with_seek_to(2, 4);
+        assert_eq!(list.next(), Some("3".to_string()));
+        assert_eq!(list.line_index(), 3);
+        assert_eq!(list.bytes_offset(), 6);
+    }
+
+    #[test]
+    fn buffer_list_should_seek_to_line() {
+        let mut list = BufferList::new(BufReader::new(Cursor::new("one\ntwo\nthree\n")));
+        assert_eq!(list.seek_to_line(2).unwrap(), 8);
+        assert_eq!(list.line_index(), 2);
+        assert_eq!(list.next(), Some("three".to_string()));
+        assert_eq!(list.bytes_offset(), 14);
+
+        assert_eq!(list.seek_to_line(0).unwrap(), 0);
+        assert_eq!(list.collect::<Vec<String>>(), ["one", "two", "three"]);
+    }
+
+    #[test]
+    fn buffer_list_seek_to_line_should_fail_past_the_last_line() {
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.next();
+        let e = list.seek_to_line(5).unwrap_err();
+        assert_eq!(
+            e,
+            IterManError::StreamOutOfBounds {
+                line_index: 5,
+                bytes_offset: 6,
+                max_len: 6,
+            }
+        );
+        assert_eq!(list.line_index(), 1);
+        assert_eq!(list.next(), Some("2".to_string()));
+    }
+
+    #[test]
+    fn buffer_list_seek_should_return_false_if_out_of_bounds() {
+        let reader = mock_buffer_reader();
+        let mut list = BufferList::new(reader);
+        let e = list.seek(7, 50).unwrap_err();
+        assert_eq!(
+            e,
+            IterManError::StreamOutOfBounds {
+                line_index: 7,
+                bytes_offset: 50,
+                max_len: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn memory_list_should_peek_without_consuming() {
+        let mut list = MemoryList::new(vec![2, 3]);
+        assert_eq!(list.peek(), Some(2));
+        assert_eq!(list.peek(), Some(2));
+        assert_eq!(list.next(), Some(2));
+        assert_eq!(list.next(), Some(3));
+        assert_eq!(list.peek(), None);
+        assert_eq!(list.line_index(), 2);
+    }
+
+    #[test]
+    fn memory_list_should_peek_across_round_robin() {
+        let mut list = MemoryList::new_round_robin(vec![2, 3]).with_seek_to(1);
+        assert_eq!(list.next(), Some(3));
+        assert_eq!(list.peek(), Some(2));
+        assert_eq!(list.next(), Some(2));
+    }
+
+    #[test]
+    fn buffer_list_should_peek_without_consuming() {
+        let mut list = BufferList::new(mock_buffer_reader());
+        assert_eq!(list.peek(), Some("1".to_string()));
+        assert_eq!(list.peek(), Some("1".to_string()));
+        assert_eq!(list.line_index(), 0);
+        assert_eq!(list.bytes_offset(), 0);
+        assert_eq!(list.next(), Some("1".to_string()));
+        assert_eq!(list.line_index(), 1);
+        assert_eq!(list.bytes_offset(), 2);
+        assert_eq!(list.collect::<Vec<String>>(), ["2", "3"]);
+    }
+
+    #[test]
+    fn buffer_list_peek_at_eof_should_not_move_cursor() {
+        let mut list = BufferList::new(mock_buffer_reader()).with_seek_to(3, 6);
+        assert_eq!(list.peek(), None);
+        assert_eq!(list.line_index(), 3);
+        assert_eq!(list.bytes_offset(), 6);
+        assert_eq!(list.next(), None);
+    }
+
+    #[test]
+    fn buffer_list_seek_should_discard_peeked_line() {
+        let mut list = BufferList::new(mock_buffer_reader());
+        assert_eq!(list.peek(), Some("1".to_string()));
+        list.seek(2, 4).unwrap();
+        assert_eq!(list.next(), Some("3".to_string()));
+    }
+
+    #[test]
+    fn buffer_list_reset_should_discard_peeked_line() {
+        let mut list = BufferList::new(mock_buffer_reader());
+        assert_eq!(list.next(), Some("1".to_string()));
+        assert_eq!(list.peek(), Some("2".to_string()));
+        list.reset();
+        assert_eq!(list.next(), Some("1".to_string()));
+        assert_eq!(list.line_index(), 1);
+        assert_eq!(list.bytes_offset(), 2);
+        assert_eq!(list.peek(), Some("2".to_string()));
+        assert_eq!(list.try_reset(), Ok(()));
+        assert_eq!(list.line_index(), 0);
+        assert_eq!(list.collect::<Vec<String>>(), ["1", "2", "3"]);
+
+        let reader = BufReader::new(FailingSeek(Cursor::new(b"1\n2\n".to_vec())));
+        let mut list = BufferList::new(reader);
+        list.next();
+        assert!(matches!(list.try_reset(), Err(IterManError::Io(_))));
+        assert_eq!((list.line_index(), list.bytes_offset()), (1, 2));
+    }
+
+    #[test]
+    fn array_lists_should_peek_without_consuming() {
+        let mut list = MemoryArrayList::new(vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(list.next(), Some(1));
+        assert_eq!(list.peek(), Some(3));
+        assert_eq!(list.next(), Some(3));
+
+        let a = BufferList::new(mock_buffer_reader());
+        let b = BufferList::new(BufReader::new(Cursor::new("4\n")));
+        let mut list = BufferArrayList::new(vec![a, b]);
+        assert_eq!(list.peek(), Some("1".to_string()));
+        assert_eq!(list.collect::<Vec<String>>(), ["1", "4", "2", "3"]);
+    }
+
+    #[test]
+    fn memory_list_should_save_and_restore_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("list.state");
+
+        let mut list = MemoryList::new(vec![2, 3, 4]);
+        list.next();
+        list.save_state(&path).unwrap();
+
+        let mut list = MemoryList::new(vec![2, 3, 4]);
+        list.restore_state(&path).unwrap();
+        assert_eq!(list.line_index(), 1);
+        assert_eq!(list.collect::<Vec<i32>>(), [3, 4]);
+    }
+
+    #[test]
+    fn memory_list_should_restore_state_when_exhausted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("list.state");
+
+        let mut list = MemoryList::new(vec![2, 3]);
+        list.by_ref().for_each(drop);
+        list.save_state(&path).unwrap();
+
+        let mut list = MemoryList::new(vec![2, 3]);
+        list.restore_state(&path).unwrap();
+        assert_eq!(list.next(), None);
+    }
+
+    #[test]
+    fn buffer_list_should_save_and_restore_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("buffer.state");
+
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.next();
+        list.next();
+        list.save_state(&path).unwrap();
+
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.restore_state(&path).unwrap();
+        assert_eq!(list.line_index(), 2);
+        assert_eq!(list.bytes_offset(), 4);
+        assert_eq!(list.collect::<Vec<String>>(), ["3"]);
+    }
+
+    #[test]
+    fn buffer_list_restore_state_should_fail_if_stream_shrank() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("buffer.state");
+
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.by_ref().for_each(drop);
+        list.save_state(&path).unwrap();
+
+        let mut list = BufferList::new(BufReader::new(Cursor::new("1\n")));
+        let e = list.restore_state(&path).unwrap_err();
+        assert_eq!(
+            e,
+            IterManError::StreamOutOfBounds {
+                line_index: 3,
+                bytes_offset: 6,
+                max_len: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn save_state_should_write_through_a_sibling_temp_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let list = MemoryList::new(vec![2, 3, 4]);
+        // A stale temp file for `list.state` must not be picked up by `list.tmp`.
+        std::fs::write(dir.path().join("list.tmp"), "stale").unwrap();
+        list.save_state(&dir.path().join("list.state")).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("list.tmp")).unwrap(),
+            "stale"
+        );
+
+        list.save_state(&dir.path().join("list.tmp")).unwrap();
+        let mut files = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(files, ["list.state", "list.tmp"]);
     }
-}
-
-impl<T: Clone> ListLike for MemoryArrayList<T> {
-    type Item = T;
 
-    fn iter(&mut self) -> Option<Self::Item> {
-        let mut cur_list_index = self.cur_list_index.load(Ordering::Relaxed);
-        if cur_list_index >= self.lists.lock().unwrap().len() {
-            self.cur_list_index.store(0, Ordering::Relaxed);
-            cur_list_index = self.cur_list_index.load(Ordering::Relaxed);
-        }
+    #[test]
+    fn restore_state_should_fail_on_missing_or_invalid_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("list.state");
 
-        let mut line_indexes = self.line_indexes.lock().unwrap();
-        let cur_line_index = line_indexes[cur_list_index];
+        let mut list = MemoryList::new(vec![2, 3, 4]);
+        assert!(matches!(
+            list.restore_state(&path),
+            Err(IterManError::Io(_))
+        ));
 
-        let lists = &self.lists.lock().unwrap();
-        if cur_line_index < lists[cur_list_index].len() {
-            let val = lists[cur_list_index][cur_line_index].clone();
+        std::fs::write(&path, "abc").unwrap();
+        assert!(matches!(
+            list.restore_state(&path),
+            Err(IterManError::Io(_))
+        ));
+    }
 
-            line_indexes[cur_list_index] += 1;
-            if self.round_robin && line_indexes[cur_list_index] >= lists[cur_list_index].len() {
-                line_indexes[cur_list_index] = 0;
+    #[test]
+    fn memory_list_should_apply_cursor() {
+        let mut list = MemoryList::new(vec![2, 3, 4]);
+        list.next();
+        let state = list.cursor();
+        assert_eq!(
+            state,
+            CursorState {
+                line_index: 1,
+                bytes_offset: None
             }
+        );
+        list.next();
+        list.apply_cursor(state).unwrap();
+        assert_eq!(list.next(), Some(3));
+    }
 
-            self.cur_list_index.fetch_add(1, Ordering::SeqCst);
-            return Some(val);
-        } else {
-            if !self.round_robin {
-                self.finished_count.fetch_add(1, Ordering::SeqCst);
-                if self.finished_count.load(Ordering::Relaxed) >= lists.len() {
-                    return None;
-                }
-            }
-        }
+    #[test]
+    fn buffer_list_should_apply_cursor() {
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.next();
+        let state = list.cursor();
+        list.next();
+        list.apply_cursor(state).unwrap();
+        assert_eq!(list.next(), Some("2".to_string()));
 
-        None
+        list.apply_cursor(CursorState {
+            line_index: 2,
+            bytes_offset: None,
+        })
+        .unwrap();
+        assert_eq!(list.bytes_offset(), 4);
+        assert_eq!(list.next(), Some("3".to_string()));
     }
-}
 
-pub struct BufferArrayList<T: Read + Seek> {
-    buf_reader: Arc<Mutex<Vec<BufferList<T>>>>,
-    finished: AtomicUsize,
-    round_robin: bool,
-    arr_index: AtomicUsize,
-    line_indexes: Arc<Mutex<Vec<usize>>>,
-    bytes_offset: AtomicUsize,
-}
+    #[test]
+    fn buffer_list_seek_percent_should_land_on_a_line_boundary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lines.txt");
+        let text = (0..100).map(|i| format!("line {i}\n")).collect::<String>();
+        std::fs::write(&path, &text).unwrap();
 
-impl<T: Read + Seek> BufferArrayList<T> {
-    pub fn new(buf_arr: Vec<BufferList<T>>) -> Self {
-        let buf_len = &buf_arr.len();
-        Self {
-            buf_reader: Arc::new(Mutex::new(buf_arr)),
-            round_robin: false,
-            finished: AtomicUsize::new(0),
-            arr_index: AtomicUsize::new(0),
-            line_indexes: Arc::new(Mutex::new(vec![0; *buf_len])),
-            bytes_offset: AtomicUsize::new(0),
-        }
-    }
-}
+        let mut list = BufferList::from_path(&path).unwrap();
+        let bytes_offset = list.seek_percent(0.5).unwrap();
+        assert!(bytes_offset >= text.len() / 2);
+        assert_eq!(text.as_bytes()[bytes_offset - 1], b'\n');
 
-impl<T: Read + Seek> Iterator for BufferArrayList<T>
-where
-    T: Read + Seek,
-{
-    type Item = String;
+        let line_index = list.line_index();
+        assert_eq!(list.next(), Some(format!("line {line_index}")));
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let string = String::new();
-        Some(string)
-    }
-}
+        assert_eq!(list.seek_percent(0.0), Ok(0));
+        assert_eq!(list.next(), Some("line 0".to_string()));
+        assert_eq!(list.seek_percent(1.0), Ok(text.len()));
+        assert_eq!(list.line_index(), 100);
+        assert_eq!(list.next(), None);
 
-/// Create a [MemoryList] from a directory by reading each file into memory.
-/// # Examples
-/// ```no-run
-/// let list = mem_list_from_dir("src", false).unwrap();
-/// assert_eq!(list.collect::<Vec<String>>().len(), 4);
-/// ```
-/// # Errors
-/// This function will return an error if the path is not a directory.
-pub fn mem_list_from_dir(
-    path: &str,
-    round_robin: bool,
-) -> Result<MemoryList<String>, std::io::Error> {
-    let mut files = vec![];
-    for entry in std::fs::read_dir(path)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_file() && !path.is_symlink() {
-            let contents = std::fs::read_to_string(path)?;
-            files.push(contents);
-        }
+        assert_eq!(
+            list.seek_percent(1.5),
+            Err(IterManError::InvalidPercent(1.5))
+        );
+        assert_eq!(
+            list.seek_percent(-0.1),
+            Err(IterManError::InvalidPercent(-0.1))
+        );
     }
 
-    if round_robin {
-        return Ok(MemoryList::new_round_robin(files));
+    #[test]
+    fn memory_list_total_len_should_not_consume() {
+        let mut list = MemoryList::new(vec![1, 2, 3]);
+        list.next();
+        assert_eq!(list.total_len(), 3);
+        assert_eq!(list.remaining(), 2);
     }
-    Ok(MemoryList::new(files))
-}
 
-/// Create a [MemoryList] from a string by splitting it into chunks.
-/// # Examples
-/// ```no-run
-/// let text = "hello world";
-/// let list = mem_list_from_chunks(text, 5, true).unwrap();
-/// assert_eq!(
-///    list.take(6).collect::<Vec<String>>(),
-///   ["hello", " worl", "d", "hello", " worl", "d"]
-/// );
-/// ```
-pub fn mem_list_from_chunks(
-    text: &str,
-    chunk_by: usize,
-    round_robin: bool,
-) -> Result<MemoryList<String>, std::io::Error> {
-    let mut chunks = vec![];
-    for chunk in text.as_bytes().chunks(chunk_by) {
-        chunks.push(String::from_utf8(chunk.to_vec()).unwrap());
+    #[test]
+    fn memory_list_get_should_not_move_the_cursor() {
+        let mut list = MemoryList::new(vec![1, 2, 3]);
+        list.next();
+        assert_eq!(list.get(0), Some(1));
+        assert_eq!(list.get(2), Some(3));
+        assert_eq!(list.get(3), None);
+        assert_eq!(list.get(usize::MAX), None);
+        assert_eq!(list.line_index(), 1);
+        assert_eq!(list.next(), Some(2));
     }
 
-    if round_robin {
-        return Ok(MemoryList::new_round_robin(chunks));
-    }
-    Ok(MemoryList::new(chunks))
-}
+    #[test]
+    fn buffer_list_count_lines_should_keep_the_cursor() {
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.next();
+        assert_eq!(list.count_lines(), Ok(3));
+        assert_eq!(list.line_index(), 1);
+        assert_eq!(list.bytes_offset(), 2);
+        assert_eq!(list.next(), Some("2".to_string()));
 
-#[cfg(test)]
-mod tests {
-    use std::io::Cursor;
+        assert_eq!(list.peek(), Some("3".to_string()));
+        assert_eq!(list.count_lines(), Ok(3));
+        assert_eq!(list.line_index(), 2);
+        assert_eq!(list.next(), Some("3".to_string()));
+        assert_eq!(list.bytes_offset(), 6);
+        assert_eq!(list.next(), None);
 
-    use super::*;
+        let mut list = BufferList::new(BufReader::new(Cursor::new("a\nb")));
+        assert_eq!(list.count_lines(), Ok(2));
+        assert_eq!(list.collect::<Vec<String>>(), ["a", "b"]);
+    }
 
     #[test]
-    #[ignore]
-    fn it_should_create_buffer_array_list() {
-        let reader = mock_buffer_reader();
-        let buf_reader = BufferList::new(reader);
-        let list = BufferArrayList::new(vec![buf_reader]);
-        assert_eq!(list.collect::<Vec<String>>(), ["1", "2", "3"]);
+    fn memory_list_should_build_from_vec_and_iterator() {
+        let list = MemoryList::from(vec![1, 2, 3]);
+        assert_eq!(list.collect::<Vec<i32>>(), [1, 2, 3]);
+
+        let list: MemoryList<i32> = (0..10).collect();
+        assert_eq!(list.remaining(), 10);
+        assert_eq!(list.collect::<Vec<i32>>(), (0..10).collect::<Vec<i32>>());
     }
 
     #[test]
-    fn it_should_create_memory_array_lists() {
-        let mem_arr = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
-        let list = MemoryArrayList::new(mem_arr);
-        assert_eq!(
-            list.take(15).collect::<Vec<i32>>(),
-            [1, 4, 7, 2, 5, 8, 3, 6, 9]
-        );
+    fn memory_list_should_yield_items_pushed_after_exhaustion() {
+        let mut list = MemoryList::new(vec![1]);
+        let mut producer = list.clone();
+        assert_eq!(list.next(), Some(1));
+        assert_eq!(list.next(), None);
+
+        producer.push(2);
+        assert_eq!(list.next(), Some(2));
+        producer.extend(vec![3, 4]);
+        assert_eq!(list.remaining(), 2);
+        assert_eq!(list.collect::<Vec<i32>>(), [3, 4]);
     }
 
     #[test]
-    fn it_should_create_memory_array_lists_with_round_robin() {
-        let mem_arr = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
-        let list = MemoryArrayList::new_round_robin(mem_arr);
-        assert_eq!(
-            list.take(15).collect::<Vec<i32>>(),
-            [1, 4, 7, 2, 5, 8, 3, 6, 9, 1, 4, 7, 2, 5, 8]
-        );
+    fn memory_list_should_recover_from_a_poisoned_lock() {
+        let mut list = MemoryList::new(vec![1, 2, 3]);
+        let shared = list.clone();
+        std::thread::spawn(move || {
+            let _guard = shared.vec.lock().unwrap();
+            panic!("poison the list");
+        })
+        .join()
+        .unwrap_err();
+
+        assert!(list.vec.is_poisoned());
+        assert_eq!(list.next(), Some(1));
+        assert_eq!(list.seek(2), Ok(2));
+        assert_eq!(list.collect::<Vec<i32>>(), [3]);
     }
 
     #[test]
-    #[ignore]
-    fn should_from_dir() {
-        let dir = mem_list_from_dir("src", false).unwrap();
-        assert_eq!(dir.collect::<Vec<String>>().len(), 4);
-        // buffer iterator
-        let reader = BufReader::new(Cursor::new("1\n2\n3\n"));
-        let list = BufferList::new(reader); // will reach EOF and stop
-        assert_eq!(list.collect::<Vec<String>>(), ["1", "2", "3"]);
+    fn buffer_list_should_error_on_a_poisoned_reader() {
+        struct PanickingReader;
+        impl Read for PanickingReader {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                panic!("read failed");
+            }
+        }
+        impl Seek for PanickingReader {
+            fn seek(&mut self, _: SeekFrom) -> std::io::Result<u64> {
+                Ok(0)
+            }
+        }
+
+        let mut list = BufferList::new(BufReader::new(PanickingReader));
+        let mut shared = list.clone();
+        std::thread::spawn(move || shared.next())
+            .join()
+            .unwrap_err();
+
+        assert_eq!(list.next(), None);
+        assert!(matches!(list.seek(0, 0), Err(IterManError::Poisoned(_))));
+        assert!(matches!(
+            list.seek_to_line(0),
+            Err(IterManError::Poisoned(_))
+        ));
     }
 
     #[test]
-    fn it_should_create_a_mem_list_by_chunks() {
-        let text = "hello world";
-        let list = mem_list_from_chunks(text, 5, true).unwrap();
+    fn try_next_should_separate_errors_from_the_end() {
+        struct FailingReader(usize);
+        impl Read for FailingReader {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                if self.0 == 0 {
+                    return Err(std::io::Error::new(std::io::ErrorKind::Other, "disk error"));
+                }
+                self.0 -= 1;
+                buf[0] = b'\n';
+                Ok(1)
+            }
+        }
+        impl Seek for FailingReader {
+            fn seek(&mut self, _: SeekFrom) -> std::io::Result<u64> {
+                Ok(0)
+            }
+        }
+
+        let mut list = BufferList::new(BufReader::with_capacity(1, FailingReader(1)));
+        assert_eq!(list.try_next(), Ok(Some(String::new())));
         assert_eq!(
-            list.take(6).collect::<Vec<String>>(),
-            ["hello", " worl", "d", "hello", " worl", "d"]
+            list.try_next(),
+            Err(IterManError::Io("disk error".to_string()))
         );
+
+        let mut list = BufferList::new(BufReader::new(Cursor::new(b"\xff\n".to_vec())));
+        assert!(matches!(list.try_next(), Err(IterManError::Utf8(_))));
+        assert_eq!(list.try_next(), Ok(None));
+
+        let mut list = BufferList::new(mock_buffer_reader());
+        assert_eq!(list.peek(), Some("1".to_string()));
+        assert_eq!(list.try_next(), Ok(Some("1".to_string())));
+
+        let mut list = MemoryList::new(vec![1]);
+        assert_eq!(list.try_next(), Ok(Some(1)));
+        assert_eq!(list.try_next(), Ok(None));
     }
 
     #[test]
-    fn memory_list_reaches_end_correctly_as_i32() {
-        let list = MemoryList::new(vec![2, 3, 4]);
-        let collected: Vec<i32> = list.collect();
-        assert_eq!(collected, [2, 3, 4]);
+    fn memory_list_into_inner_should_return_the_vec() {
+        let mut list = MemoryList::new(vec![1, 2, 3]);
+        list.next();
+        assert_eq!(list.into_inner(), [1, 2, 3]);
     }
 
     #[test]
-    fn memory_list_reaches_end_correctly_as_str() {
-        let list = MemoryList::new(vec!["2", "3", "4"]);
-        let collected: Vec<&str> = list.collect();
-        assert_eq!(collected, ["2", "3", "4"]);
+    #[should_panic(expected = "while a clone is alive")]
+    fn memory_list_into_inner_should_panic_when_shared() {
+        let list = MemoryList::new(vec![1, 2, 3]);
+        let _shared = list.clone();
+        list.into_inner();
     }
 
     #[test]
-    fn memory_list_round_robins_correctly() {
-        let list = MemoryList::new_round_robin(vec![2, 3, 4]);
-        let collected: Vec<i32> = list.take(6).collect();
-        assert_eq!(collected, [2, 3, 4, 2, 3, 4]);
+    fn buffer_list_should_parse_lines() {
+        let list = BufferList::new(BufReader::new(Cursor::new("1\n2\nx\n\n")));
+        let parsed = list.parse_lines::<i32>().collect::<Vec<_>>();
+        assert_eq!(parsed.len(), 4);
+        assert_eq!(parsed[0], Ok(1));
+        assert_eq!(parsed[1], Ok(2));
+        assert!(parsed[2].is_err());
+        assert!(parsed[3].is_err());
     }
 
     #[test]
-    fn memory_list_should_return_nothing_when_empty() {
-        let list = MemoryList::new_round_robin(vec![]);
-        let collected: Vec<i32> = list.take(10).collect();
-        assert_eq!(collected, []);
+    #[cfg(feature = "serde")]
+    fn buffer_list_should_deserialize_json_lines() {
+        #[derive(serde::Deserialize, Debug, PartialEq)]
+        struct Client {
+            name: String,
+            age: u32,
+        }
+
+        let text = "{\"name\":\"a\",\"age\":1}\n{\"name\":\"b\",\"age\":2}\n{\"name\":\n";
+        let list = BufferList::new(BufReader::new(Cursor::new(text)));
+        let clients = list.deserialize_lines::<Client>().collect::<Vec<_>>();
+        assert_eq!(clients.len(), 3);
+        assert_eq!(
+            clients[0].as_ref().unwrap(),
+            &Client {
+                name: "a".to_string(),
+                age: 1,
+            }
+        );
+        assert_eq!(
+            clients[1].as_ref().unwrap(),
+            &Client {
+                name: "b".to_string(),
+                age: 2,
+            }
+        );
+        assert!(clients[2].is_err());
     }
 
     #[test]
-    fn buffer_list_reaches_end_correctly() {
-        let reader = mock_buffer_reader();
-        let list = BufferList::new(reader);
+    fn buffer_list_read_into_should_reuse_the_buffer() {
+        let mut list = BufferList::new(BufReader::new(Cursor::new(" 1 \n2\n3")));
+        let mut line = String::with_capacity(16);
+        let capacity = line.capacity();
 
-        let collected: Vec<String> = list.collect();
-        assert_eq!(collected, ["1", "2", "3"]);
+        assert_eq!(list.read_into(&mut line), Some(4));
+        assert_eq!(line, "1");
+        line.clear();
+        assert_eq!(list.peek(), Some("2".to_string()));
+        assert_eq!(list.read_into(&mut line), Some(2));
+        assert_eq!(line, "2");
+        line.push(',');
+        assert_eq!(list.read_into(&mut line), Some(1));
+        assert_eq!(line, "2,3");
+        assert_eq!(list.read_into(&mut line), None);
+        assert_eq!(line, "2,3");
+
+        assert_eq!(line.capacity(), capacity);
+        assert_eq!(list.line_index(), 3);
+        assert_eq!(list.bytes_offset(), 7);
     }
 
     #[test]
-    fn buffer_list_round_robins_correctly() {
-        let reader = mock_buffer_reader();
-        let list = BufferList::new_round_robin(reader);
+    fn buffer_list_try_read_into_should_report_invalid_utf8() {
+        let text = b"ok\n\xff\nlast".to_vec();
+        let mut list = BufferList::new(BufReader::new(Cursor::new(text)));
+        let mut line = String::from(">");
 
-        let collected: Vec<String> = list.take(6).collect();
-        assert_eq!(collected, ["1", "2", "3", "1", "2", "3"]);
+        assert_eq!(list.try_read_into(&mut line).unwrap(), Some(3));
+        assert_eq!(line, ">ok");
+        assert!(matches!(
+            list.try_read_into(&mut line),
+            Err(IterManError::Utf8(_))
+        ));
+        assert_eq!(line, ">ok");
+        assert_eq!(list.line_index(), 2);
+        assert_eq!(list.try_read_into(&mut line).unwrap(), Some(4));
+        assert_eq!(line, ">oklast");
+        assert_eq!(list.try_read_into(&mut line).unwrap(), None);
     }
 
     #[test]
-    fn buffer_list_should_return_nothing_with_an_empty_buffer() {
-        let reader = BufReader::new(Cursor::new(""));
-        let list = BufferList::new_round_robin(reader);
+    fn buffer_list_read_into_should_take_a_peek_that_wrapped() {
+        let mut list = BufferList::new_round_robin(mock_buffer_reader());
+        list.by_ref().take(3).for_each(drop);
+        assert_eq!(list.peek(), Some("1".to_string()));
+        assert_eq!(list.bytes_offset(), 6);
 
-        let collected: Vec<String> = list.take(10).collect();
-        assert_eq!(collected.len(), 0);
+        let mut line = String::new();
+        assert_eq!(list.read_into(&mut line), Some(2));
+        assert_eq!(line, "1");
+        assert_eq!(list.line_index(), 1);
+        assert_eq!(list.bytes_offset(), 2);
     }
 
     #[test]
-    fn memory_list_should_seek() {
-        let mut list = MemoryList::new_round_robin(vec![2, 3, 4]);
-        list.seek(2).expect("TODO: panic message");
-        assert_eq!(list.next(), Some(4));
-        assert_eq!(list.line_index(), 3);
+    fn buffer_list_into_inner_should_return_the_reader() {
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.next();
+        let mut rest = String::new();
+        list.into_inner().read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "2\n3\n");
     }
 
     #[test]
-    fn memory_list_with_seek_to() {
-        let mut list = MemoryList::new_round_robin(vec![2, 3, 4]).with_seek_to(2);
-        assert_eq!(list.next(), Some(4));
+    fn memory_list_advance_should_skip_items() {
+        let mut list = MemoryList::new(vec![1, 2, 3, 4]);
+        assert_eq!(list.advance(3), 3);
         assert_eq!(list.line_index(), 3);
+        assert_eq!(list.advance(3), 1);
+        assert_eq!(list.next(), None);
+
+        let mut list = MemoryList::new_round_robin(vec![1, 2, 3]);
+        assert_eq!(list.advance(7), 7);
+        assert_eq!(list.next(), Some(2));
+
+        let mut list = MemoryList::new_round_robin_times(vec![1, 2, 3], 2);
+        assert_eq!(list.advance(10), 6);
+        assert_eq!(list.next(), None);
     }
 
     #[test]
-    fn memory_list_seek_should_return_false_if_out_of_bounds() {
-        let mut list = MemoryList::new(vec![2, 3, 4]);
-        let e = list.seek(6).unwrap_err();
+    fn buffer_list_advance_should_skip_lines() {
+        let mut list = BufferList::new(mock_buffer_reader());
+        assert_eq!(list.advance(2), Ok(2));
+        assert_eq!(list.line_index(), 2);
+        assert_eq!(list.bytes_offset(), 4);
+        assert_eq!(list.advance(5), Ok(1));
+        assert_eq!(list.bytes_offset(), 6);
+        assert_eq!(list.next(), None);
+
+        let mut list = BufferList::new_round_robin(mock_buffer_reader());
+        assert_eq!(list.peek(), Some("1".to_string()));
+        assert_eq!(list.advance(4), Ok(4));
+        assert_eq!(list.line_index(), 1);
+        assert_eq!(list.next(), Some("2".to_string()));
+    }
+
+    #[test]
+    fn memory_list_seek_by_should_move_relative_to_line_index() {
+        let mut list = MemoryList::new(vec![1, 2, 3, 4, 5]);
+        assert_eq!(list.seek_by(3), Ok(3));
+        assert_eq!(list.seek_by(-2), Ok(1));
+        assert_eq!(list.next(), Some(2));
+        assert_eq!(list.seek_by(-10), Ok(0));
         assert_eq!(
-            e,
-            IterManError::MemoryOutOfBounds {
+            list.seek_by(6),
+            Err(IterManError::MemoryOutOfBounds {
                 line_index: 6,
-                max_len: 3,
-            }
+                max_len: 5,
+            })
         );
+        assert_eq!(list.line_index(), 0);
     }
 
     #[test]
-    fn buffer_list_should_seek() {
-        let reader = mock_buffer_reader();
-        let mut list = BufferList::new(reader);
-        list.seek(2, 4).expect("TODO: panic message");
+    fn buffer_list_seek_by_should_move_relative_to_line_index() {
+        let mut list = BufferList::new(mock_buffer_reader());
+        assert_eq!(list.seek_by(2), Ok(4));
+        assert_eq!(list.line_index(), 2);
+        assert_eq!(list.seek_by(-1), Ok(2));
+        assert_eq!(list.peek(), Some("2".to_string()));
+        assert_eq!(list.seek_by(1), Ok(4));
         assert_eq!(list.next(), Some("3".to_string()));
-        assert_eq!(list.line_index(), 3);
-        assert_eq!(list.bytes_offset(), 6);
+        assert_eq!(list.seek_by(-10), Ok(0));
+        assert_eq!(
+            list.seek_by(4),
+            Err(IterManError::StreamOutOfBounds {
+                line_index: 4,
+                bytes_offset: 6,
+                max_len: 6,
+            })
+        );
+        assert_eq!(list.line_index(), 0);
+        assert_eq!(list.next(), Some("1".to_string()));
     }
 
     #[test]
-    fn buffer_list_with_seek_to() {
-        let reader = mock_buffer_reader();
-        let mut list = BufferList::new(reader).with_seek_to(2, 4);
-        assert_eq!(list.next(), Some("3".to_string()));
-        assert_eq!(list.line_index(), 3);
-        assert_eq!(list.bytes_offset(), 6);
-    }
+    fn position_should_track_next_calls() {
+        let mut list = MemoryList::new(vec![2, 3, 4]);
+        list.next();
+        list.next();
+        assert_eq!(
+            list.position(),
+            Position {
+                line_index: 2,
+                bytes_offset: None,
+            }
+        );
+        list.seek_position(Position::default()).unwrap();
+        assert_eq!(list.next(), Some(2));
 
-    #[test]
-    fn buffer_list_seek_should_return_false_if_out_of_bounds() {
-        let reader = mock_buffer_reader();
-        let mut list = BufferList::new(reader);
-        let e = list.seek(7, 50).unwrap_err();
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.next();
+        list.next();
+        let position = list.position();
         assert_eq!(
-            e,
-            IterManError::StreamOutOfBounds {
-                line_index: 7,
-                bytes_offset: 50,
-                max_len: 6,
+            position,
+            Position {
+                line_index: 2,
+                bytes_offset: Some(4),
             }
         );
+        list.next();
+        list.seek_position(position).unwrap();
+        assert_eq!(list.next(), Some("3".to_string()));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn cursor_state_should_round_trip_through_json() {
+        let mut list = BufferList::new(mock_buffer_reader());
+        list.next();
+        let json = serde_json::to_string(&list.cursor()).unwrap();
+        assert_eq!(json, r#"{"line_index":1,"bytes_offset":2}"#);
+
+        list.next();
+        let state: CursorState = serde_json::from_str(&json).unwrap();
+        list.apply_cursor(state).unwrap();
+        assert_eq!(list.collect::<Vec<String>>(), ["2", "3"]);
     }
 
     fn mock_buffer_reader<'a>() -> BufReader<Cursor<&'a str>> {
-        let reader = BufReader::new(Cursor::new("1\n2\n3\n"));
-        reader
+        BufReader::new(Cursor::new("1\n2\n3\n"))
     }
 }
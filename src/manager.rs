@@ -1,12 +1,428 @@
-use crate::list::{BufferList, MemoryList};
-use std::io::{BufReader, Cursor};
-struct Manager<'a> {
-    clients: BufferList<Cursor<&'a str>>,
-    subjects: MemoryList<&'a str>,
-    landing_pages: MemoryList<&'a str>,
+use crate::cursor::Position;
+use crate::error::IterManError;
+use crate::list::{write_atomic, BufferList, ListLike, MemoryList};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{BufReader, Cursor, Read, Seek};
+use std::path::Path;
+
+/// A list registered on a [Manager] under a name.
+type NamedList = Box<dyn ListLike<Item = String>>;
+
+/// A [Manager] holds the lists that make up a campaign: a [BufferList] of clients,
+/// [MemoryList]s of subjects and landing pages, and any number of extra named lists for other
+/// dimensions such as sender names or footers.
+/// # Examples
+/// ```no-run
+/// let mut manager = Manager::builder()
+///     .clients(BufferList::new(BufReader::new(File::open("clients.txt").unwrap())))
+///     .subjects(MemoryList::new(vec!["Hi again", "Since we last spoke"]))
+///     .landing_pages(MemoryList::new(vec!["https://business.com/lp/new"]))
+///     .build()
+///     .unwrap();
+/// assert_eq!(manager.clients_mut().next().unwrap(), "test@aol.com");
+/// ```
+pub struct Manager<R: Read + Seek, T: Clone> {
+    clients: BufferList<R>,
+    subjects: MemoryList<T>,
+    landing_pages: MemoryList<T>,
+    lists: HashMap<String, NamedList>,
+    /// Where the latest `combinations` run has got to, kept up to date as it's read.
+    combination: CombinationCursor,
+    /// Pairs of the first client to skip on the next `combinations` run, set by `restore_state`.
+    skip_pairs: usize,
+}
+
+/// The position of a `combinations` run: the client being paired, how many of its
+/// (subject, landing page) pairs have been yielded, and where the subjects and landing pages
+/// started.
+#[derive(Debug, Clone, Copy, Default)]
+struct CombinationCursor {
+    client: Position,
+    pair_index: usize,
+    subjects_start: usize,
+    landing_pages_start: usize,
+}
+
+/// The version of the file written by [Manager::save_state], bumped whenever its fields change.
+const STATE_VERSION: usize = 1;
+
+/// What [Manager::save_state] writes: a `version` line followed by one `name value` line per
+/// field, so a file from an older or newer layout is refused instead of misread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ManagerState {
+    client_line_index: usize,
+    client_bytes_offset: usize,
+    pair_index: usize,
+    subjects_start: usize,
+    subjects_len: usize,
+    landing_pages_start: usize,
+    landing_pages_len: usize,
+    clients_len: usize,
+}
+
+impl ManagerState {
+    /// Every field by name, in the order they're written.
+    fn fields(&self) -> [(&'static str, usize); 8] {
+        [
+            ("client_line_index", self.client_line_index),
+            ("client_bytes_offset", self.client_bytes_offset),
+            ("pair_index", self.pair_index),
+            ("subjects_start", self.subjects_start),
+            ("subjects_len", self.subjects_len),
+            ("landing_pages_start", self.landing_pages_start),
+            ("landing_pages_len", self.landing_pages_len),
+            ("clients_len", self.clients_len),
+        ]
+    }
+
+    fn write(&self, path: &Path) -> Result<(), IterManError> {
+        let mut contents = format!("version {STATE_VERSION}\n");
+        for (name, value) in self.fields() {
+            contents.push_str(&format!("{name} {value}\n"));
+        }
+        write_atomic(path, contents)
+    }
+
+    /// Read a file written by [ManagerState::write], which must have the current version and
+    /// every field.
+    fn read(path: &Path) -> Result<Self, IterManError> {
+        let invalid = |reason: String| {
+            IterManError::Io(format!("invalid state file {}: {reason}", path.display()))
+        };
+
+        let contents = std::fs::read_to_string(path)?;
+        let mut values = HashMap::new();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let (name, value) = line
+                .trim()
+                .split_once(' ')
+                .ok_or_else(|| invalid(format!("expected a name and a value, found {line:?}")))?;
+            let value = value
+                .trim()
+                .parse::<usize>()
+                .map_err(|e| invalid(format!("{name}: {e}")))?;
+            values.insert(name, value);
+        }
+
+        match values.get("version") {
+            Some(&STATE_VERSION) => {}
+            Some(version) => {
+                return Err(invalid(format!(
+                    "version {version}, expected {STATE_VERSION}"
+                )))
+            }
+            None => return Err(invalid("missing version".to_string())),
+        }
+        let field = |name: &str| {
+            values
+                .get(name)
+                .copied()
+                .ok_or_else(|| invalid(format!("missing {name}")))
+        };
+        Ok(Self {
+            client_line_index: field("client_line_index")?,
+            client_bytes_offset: field("client_bytes_offset")?,
+            pair_index: field("pair_index")?,
+            subjects_start: field("subjects_start")?,
+            subjects_len: field("subjects_len")?,
+            landing_pages_start: field("landing_pages_start")?,
+            landing_pages_len: field("landing_pages_len")?,
+            clients_len: field("clients_len")?,
+        })
+    }
+}
+
+/// Shows the cursor of every list, with the named lists by name only.
+impl<R: Read + Seek, T: Clone> fmt::Debug for Manager<R, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names = self.list_names().collect::<Vec<&str>>();
+        names.sort_unstable();
+        f.debug_struct("Manager")
+            .field("clients", &self.clients)
+            .field("subjects", &self.subjects)
+            .field("landing_pages", &self.landing_pages)
+            .field("lists", &names)
+            .finish()
+    }
+}
+
+impl<R: Read + Seek, T: Clone> Manager<R, T> {
+    /// Creates a [ManagerBuilder] to supply the lists from.
+    pub fn builder() -> ManagerBuilder<R, T> {
+        ManagerBuilder::default()
+    }
+
+    pub fn clients(&self) -> &BufferList<R> {
+        &self.clients
+    }
+
+    pub fn clients_mut(&mut self) -> &mut BufferList<R> {
+        &mut self.clients
+    }
+
+    pub fn subjects(&self) -> &MemoryList<T> {
+        &self.subjects
+    }
+
+    pub fn subjects_mut(&mut self) -> &mut MemoryList<T> {
+        &mut self.subjects
+    }
+
+    pub fn landing_pages(&self) -> &MemoryList<T> {
+        &self.landing_pages
+    }
+
+    pub fn landing_pages_mut(&mut self) -> &mut MemoryList<T> {
+        &mut self.landing_pages
+    }
+
+    /// Rewind the clients, subjects and landing pages to their first items, so the manager can
+    /// be reused for another full run of `combinations` or `zipped`. The named lists can't be
+    /// rewound through [ListLike], so they're left where they are.
+    /// # Examples
+    /// ```no-run
+    /// let mut manager = Manager::new();
+    /// assert_eq!(manager.combinations().count(), 18);
+    /// manager.reset_all().unwrap();
+    /// assert_eq!(manager.combinations().count(), 18);
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::Io] or [IterManError::Poisoned] if the clients can't be rewound, in
+    /// which case nothing is reset.
+    pub fn reset_all(&mut self) -> Result<(), IterManError> {
+        self.clients.try_reset()?;
+        self.subjects.reset();
+        self.landing_pages.reset();
+        self.combination = CombinationCursor::default();
+        self.skip_pairs = 0;
+        Ok(())
+    }
+
+    /// Write the position of the latest `combinations` run to `path`, so a restarted campaign
+    /// can carry on from the next unsent combination with [Manager::restore_state]. The lengths
+    /// of the clients, subjects and landing pages are saved too, to catch lists that change in
+    /// between. The file is labeled and versioned, so one written by a different layout is
+    /// refused. The named lists aren't included.
+    /// # Examples
+    /// ```no-run
+    /// let mut manager = Manager::new();
+    /// for combination in manager.combinations().take(100) {
+    ///     send(combination);
+    /// }
+    /// manager.save_state(Path::new("campaign.state")).unwrap();
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::Io] if the file can't be written or the clients can't be measured.
+    pub fn save_state(&self, path: &Path) -> Result<(), IterManError> {
+        let cursor = self.combination;
+        ManagerState {
+            client_line_index: cursor.client.line_index,
+            client_bytes_offset: cursor.client.bytes_offset.unwrap_or_default(),
+            pair_index: cursor.pair_index,
+            subjects_start: cursor.subjects_start,
+            subjects_len: self.subjects.total_len(),
+            landing_pages_start: cursor.landing_pages_start,
+            landing_pages_len: self.landing_pages.total_len(),
+            clients_len: self.clients.stream_len()?,
+        }
+        .write(path)
+    }
+
+    /// Read a position written by [Manager::save_state] and move the lists back to it, so the
+    /// next `combinations` run starts with the first combination that hadn't been yielded.
+    /// # Examples
+    /// ```no-run
+    /// let mut manager = Manager::new();
+    /// manager.restore_state(Path::new("campaign.state")).unwrap();
+    /// for combination in manager.combinations() {
+    ///     send(combination);
+    /// }
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::ListLengthChanged] if any of the lists is a different length than
+    /// when the state was saved, [IterManError::Io] if the file can't be read or has a different
+    /// version, or
+    /// [IterManError::StreamOutOfBounds] if the clients can't be seeked back.
+    pub fn restore_state(&mut self, path: &Path) -> Result<(), IterManError> {
+        let state = ManagerState::read(path)?;
+        let lengths = [
+            ("subjects", state.subjects_len, self.subjects.total_len()),
+            (
+                "landing_pages",
+                state.landing_pages_len,
+                self.landing_pages.total_len(),
+            ),
+            ("clients", state.clients_len, self.clients.stream_len()?),
+        ];
+        for (list, saved, found) in lengths {
+            if saved != found {
+                return Err(IterManError::ListLengthChanged {
+                    list: list.to_string(),
+                    saved,
+                    found,
+                });
+            }
+        }
+
+        self.clients
+            .seek(state.client_line_index, state.client_bytes_offset)?;
+        self.subjects.seek(state.subjects_start)?;
+        self.landing_pages.seek(state.landing_pages_start)?;
+        self.combination = CombinationCursor {
+            client: self.clients.position(),
+            pair_index: state.pair_index,
+            subjects_start: state.subjects_start,
+            landing_pages_start: state.landing_pages_start,
+        };
+        self.skip_pairs = state.pair_index;
+        Ok(())
+    }
+
+    /// Register `list` under `name`, returning the list it replaced if there was one.
+    /// # Examples
+    /// ```no-run
+    /// let mut manager = Manager::new();
+    /// manager.add_list("footers", MemoryList::new(vec!["Cheers".to_string()]));
+    /// assert_eq!(manager.next_of("footers"), Some("Cheers".to_string()));
+    /// ```
+    pub fn add_list<L: ListLike<Item = String> + 'static>(
+        &mut self,
+        name: &str,
+        list: L,
+    ) -> Option<NamedList> {
+        self.lists.insert(name.to_string(), Box::new(list))
+    }
+
+    /// Unregister the list named `name`, returning it.
+    pub fn remove_list(&mut self, name: &str) -> Option<NamedList> {
+        self.lists.remove(name)
+    }
+
+    pub fn list_mut(&mut self, name: &str) -> Option<&mut NamedList> {
+        self.lists.get_mut(name)
+    }
+
+    /// The names of the registered lists, in no particular order.
+    pub fn list_names(&self) -> impl Iterator<Item = &str> {
+        self.lists.keys().map(String::as_str)
+    }
+
+    /// Read the next item of the list named `name`. Returns [None] if it's exhausted or no list
+    /// is registered under that name.
+    pub fn next_of(&mut self, name: &str) -> Option<String> {
+        self.lists.get_mut(name)?.iter()
+    }
+
+    /// Like [Manager::zipped] over the registered lists named in `names`, yielding their items in
+    /// the same order as the names until any of them runs out.
+    /// # Examples
+    /// ```no-run
+    /// let mut manager = Manager::new();
+    /// manager.add_list("senders", MemoryList::new(vec!["Ann".to_string(), "Bo".to_string()]));
+    /// manager.add_list("footers", MemoryList::new(vec!["Cheers".to_string()]));
+    /// let rows = manager.zipped_of(&["senders", "footers"]).unwrap();
+    /// assert_eq!(rows.collect::<Vec<_>>(), [vec!["Ann", "Cheers"]]);
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::MissingList] naming the first name that isn't registered, or that
+    /// appears more than once.
+    pub fn zipped_of(
+        &mut self,
+        names: &[&str],
+    ) -> Result<impl Iterator<Item = Vec<String>> + '_, IterManError> {
+        let mut lists = self.named_lists_mut(names)?;
+        Ok(std::iter::from_fn(move || {
+            if lists.is_empty() {
+                return None;
+            }
+            lists.iter_mut().map(|list| list.iter()).collect()
+        }))
+    }
+
+    /// Like [Manager::combinations] over the registered lists named in `names`, yielding every
+    /// combination of their items in the same order as the names, with the first list outermost.
+    /// The first list is read lazily while the rest are read into memory up front, so a
+    /// `round_robin` list anywhere but first never ends. Nothing is yielded if any list is empty.
+    /// The position isn't tracked for [Manager::save_state].
+    /// # Examples
+    /// ```no-run
+    /// let mut manager = Manager::new();
+    /// manager.add_list("senders", MemoryList::new(vec!["Ann".to_string(), "Bo".to_string()]));
+    /// manager.add_list("footers", MemoryList::new(vec!["Cheers".to_string(), "Bye".to_string()]));
+    /// let rows = manager.combinations_of(&["senders", "footers"]).unwrap();
+    /// assert_eq!(
+    ///     rows.collect::<Vec<_>>(),
+    ///     [["Ann", "Cheers"], ["Ann", "Bye"], ["Bo", "Cheers"], ["Bo", "Bye"]]
+    /// );
+    /// ```
+    /// # Errors
+    /// Returns [IterManError::MissingList] naming the first name that isn't registered, or that
+    /// appears more than once.
+    pub fn combinations_of(
+        &mut self,
+        names: &[&str],
+    ) -> Result<impl Iterator<Item = Vec<String>> + '_, IterManError> {
+        let mut lists = self.named_lists_mut(names)?.into_iter();
+        let outer = lists.next();
+
+        // Every combination of the inner lists' items, built up one list at a time.
+        let mut rows = vec![Vec::new()];
+        for list in lists {
+            let items = std::iter::from_fn(|| list.iter()).collect::<Vec<String>>();
+            rows = rows
+                .iter()
+                .flat_map(|row| {
+                    items.iter().map(move |item| {
+                        let mut row = row.clone();
+                        row.push(item.clone());
+                        row
+                    })
+                })
+                .collect();
+        }
+
+        let mut outer = outer.filter(|_| !rows.is_empty());
+        let mut row_index = rows.len();
+        let mut item = String::new();
+        Ok(std::iter::from_fn(move || {
+            // Don't touch the first list at all when there is nothing to pair it with.
+            let outer = outer.as_mut()?;
+            if row_index == rows.len() {
+                item = outer.iter()?;
+                row_index = 0;
+            }
+
+            let mut row = Vec::with_capacity(rows[row_index].len() + 1);
+            row.push(item.clone());
+            row.extend_from_slice(&rows[row_index]);
+            row_index += 1;
+            Some(row)
+        }))
+    }
+
+    /// The registered lists named in `names`, in the same order.
+    fn named_lists_mut(&mut self, names: &[&str]) -> Result<Vec<&mut NamedList>, IterManError> {
+        let mut lists = names
+            .iter()
+            .map(|_| None)
+            .collect::<Vec<Option<&mut NamedList>>>();
+        for (name, list) in self.lists.iter_mut() {
+            if let Some(i) = names.iter().position(|n| n == name) {
+                lists[i] = Some(list);
+            }
+        }
+
+        lists
+            .into_iter()
+            .zip(names)
+            .map(|(list, name)| list.ok_or_else(|| IterManError::MissingList(name.to_string())))
+            .collect()
+    }
 }
 
-impl Manager<'_> {
+impl Manager<Cursor<&'static str>, &'static str> {
+    /// Creates a demo [Manager] filled with sample data.
     pub fn new() -> Self {
         Self {
             clients: BufferList::new(BufReader::new(Cursor::new(
@@ -18,10 +434,161 @@ impl Manager<'_> {
                 "https://business.com/lp/current",
                 "https://business.com/lp/best",
             ]),
+            lists: HashMap::new(),
+            combination: CombinationCursor::default(),
+            skip_pairs: 0,
         }
     }
 }
 
+impl Default for Manager<Cursor<&'static str>, &'static str> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Read + Seek, T: Clone + ToString> Manager<R, T> {
+    /// Yields every (client, subject, landing page) combination with the client outermost.
+    /// Clients are read lazily while the remaining subjects and landing pages are read into memory
+    /// up front, so every list is consumed. Nothing is yielded if any list is empty. The position
+    /// is tracked as the combinations are read, for [Manager::save_state].
+    /// # Examples
+    /// ```no-run
+    /// let mut manager = Manager::new();
+    /// assert_eq!(manager.combinations().count(), 18);
+    /// ```
+    pub fn combinations(&mut self) -> Box<dyn Iterator<Item = (String, String, String)> + '_> {
+        let subjects_start = self.subjects.line_index();
+        let landing_pages_start = self.landing_pages.line_index();
+        let remaining = self.subjects.remaining();
+        let subjects = self.subjects.by_ref().take(remaining).collect::<Vec<T>>();
+        let remaining = self.landing_pages.remaining();
+        let landing_pages = self
+            .landing_pages
+            .by_ref()
+            .take(remaining)
+            .collect::<Vec<T>>();
+
+        let pairs = subjects
+            .iter()
+            .flat_map(|subject| {
+                landing_pages
+                    .iter()
+                    .map(|landing_page| (subject.to_string(), landing_page.to_string()))
+            })
+            .collect::<Vec<(String, String)>>();
+
+        let mut pair_index = std::mem::take(&mut self.skip_pairs);
+        self.combination = CombinationCursor {
+            client: self.clients.position(),
+            pair_index,
+            subjects_start,
+            landing_pages_start,
+        };
+
+        let clients = &mut self.clients;
+        let cursor = &mut self.combination;
+        let mut client: Option<String> = None;
+        Box::new(std::iter::from_fn(move || loop {
+            // Don't touch the clients at all when there is nothing to pair them with.
+            if pairs.is_empty() {
+                return None;
+            }
+            if let Some(client) = &client {
+                if let Some((subject, landing_page)) = pairs.get(pair_index) {
+                    pair_index += 1;
+                    cursor.pair_index = pair_index;
+                    return Some((client.clone(), subject.clone(), landing_page.clone()));
+                }
+                // A restored run may start partway into its first client, so only reset after it.
+                pair_index = 0;
+                cursor.pair_index = 0;
+            }
+
+            cursor.client = BufferList::position(clients);
+            client = Some(clients.next()?);
+        }))
+    }
+
+    /// Yields the nth client with the nth subject and the nth landing page, reading one item from
+    /// each list per step in client, subject, landing page order and stopping as soon as any of
+    /// them runs out. An item read from an earlier list in that final step is dropped.
+    /// # Examples
+    /// ```no-run
+    /// let mut manager = Manager::new();
+    /// assert_eq!(manager.zipped().count(), 2);
+    /// ```
+    pub fn zipped(&mut self) -> impl Iterator<Item = (String, String, String)> + '_ {
+        std::iter::from_fn(move || {
+            let client = self.clients.next()?;
+            let subject = self.subjects.next()?;
+            let landing_page = self.landing_pages.next()?;
+            Some((client, subject.to_string(), landing_page.to_string()))
+        })
+    }
+}
+
+/// Builds a [Manager] from caller supplied lists.
+pub struct ManagerBuilder<R: Read + Seek, T: Clone> {
+    clients: Option<BufferList<R>>,
+    subjects: Option<MemoryList<T>>,
+    landing_pages: Option<MemoryList<T>>,
+    lists: HashMap<String, NamedList>,
+}
+
+impl<R: Read + Seek, T: Clone> Default for ManagerBuilder<R, T> {
+    fn default() -> Self {
+        Self {
+            clients: None,
+            subjects: None,
+            landing_pages: None,
+            lists: HashMap::new(),
+        }
+    }
+}
+
+impl<R: Read + Seek, T: Clone> ManagerBuilder<R, T> {
+    pub fn clients(mut self, clients: BufferList<R>) -> Self {
+        self.clients = Some(clients);
+        self
+    }
+
+    pub fn subjects(mut self, subjects: MemoryList<T>) -> Self {
+        self.subjects = Some(subjects);
+        self
+    }
+
+    pub fn landing_pages(mut self, landing_pages: MemoryList<T>) -> Self {
+        self.landing_pages = Some(landing_pages);
+        self
+    }
+
+    /// Register an extra named list, see [Manager::add_list].
+    pub fn list<L: ListLike<Item = String> + 'static>(mut self, name: &str, list: L) -> Self {
+        self.lists.insert(name.to_string(), Box::new(list));
+        self
+    }
+
+    /// # Errors
+    /// Returns [IterManError::MissingList] naming the first list that wasn't supplied.
+    pub fn build(self) -> Result<Manager<R, T>, IterManError> {
+        Ok(Manager {
+            clients: self
+                .clients
+                .ok_or_else(|| IterManError::MissingList("clients".to_string()))?,
+            subjects: self
+                .subjects
+                .ok_or_else(|| IterManError::MissingList("subjects".to_string()))?,
+            landing_pages: self
+                .landing_pages
+                .ok_or_else(|| IterManError::MissingList("landing_pages".to_string()))?,
+            lists: self.lists,
+            combination: CombinationCursor::default(),
+            skip_pairs: 0,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -31,6 +598,16 @@ mod tests {
         let _ = Manager::new();
     }
 
+    #[test]
+    fn it_should_debug_a_manager() {
+        let mut manager = Manager::new();
+        manager.add_list("footers", MemoryList::new(vec!["Cheers".to_string()]));
+        manager.clients_mut().next();
+        let debug = format!("{manager:?}");
+        assert!(debug.contains("clients: BufferList { round_robin: false, line_index: 1"));
+        assert!(debug.contains(r#"lists: ["footers"]"#));
+    }
+
     #[test]
     fn it_should_add_list_to_manager() {
         let mut manager = Manager::new();
@@ -38,10 +615,277 @@ mod tests {
 
         assert_eq!(manager.subjects.next().unwrap(), "Hi again");
 
-        let collection: Vec<&str> = manager.landing_pages.into_iter().collect();
+        let collection: Vec<&str> = manager.landing_pages.collect();
         assert_eq!(collection.len(), 3);
         assert_eq!(collection[0], "https://business.com/lp/new");
         assert_eq!(collection[1], "https://business.com/lp/current");
         assert_eq!(collection[2], "https://business.com/lp/best");
     }
+
+    #[test]
+    fn it_should_yield_every_combination() {
+        let mut manager = Manager::new();
+        let combinations = manager.combinations().collect::<Vec<_>>();
+        assert_eq!(combinations.len(), 3 * 2 * 3);
+        assert_eq!(
+            combinations[0],
+            (
+                "test@aol.com".to_string(),
+                "Hi again".to_string(),
+                "https://business.com/lp/new".to_string()
+            )
+        );
+        assert_eq!(
+            combinations[6],
+            (
+                "test@web.com".to_string(),
+                "Hi again".to_string(),
+                "https://business.com/lp/new".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_yield_every_combination_again_after_reset_all() {
+        let mut manager = Manager::new();
+        let first = manager.combinations().collect::<Vec<_>>();
+        assert_eq!(manager.combinations().count(), 0);
+
+        assert_eq!(manager.reset_all(), Ok(()));
+        assert_eq!(manager.combinations().collect::<Vec<_>>(), first);
+
+        assert_eq!(manager.reset_all(), Ok(()));
+        assert_eq!(manager.zipped().count(), 2);
+    }
+
+    #[test]
+    fn it_should_resume_combinations_from_saved_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("campaign.state");
+        let all = Manager::new().combinations().collect::<Vec<_>>();
+
+        for sent in [0, 6, 9, 17, 18] {
+            let mut manager = Manager::new();
+            let first = manager.combinations().take(sent).collect::<Vec<_>>();
+            manager.save_state(&path).unwrap();
+
+            let mut manager = Manager::new();
+            manager.restore_state(&path).unwrap();
+            let rest = manager.combinations().collect::<Vec<_>>();
+            assert_eq!([first, rest].concat(), all, "resumed after {sent}");
+        }
+    }
+
+    #[test]
+    fn it_should_refuse_to_restore_state_when_a_list_changed_length() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("campaign.state");
+        let mut manager = Manager::new();
+        manager.combinations().take(4).for_each(drop);
+        manager.save_state(&path).unwrap();
+
+        let mut manager = Manager {
+            subjects: MemoryList::new(vec!["Hi again"]),
+            ..Manager::new()
+        };
+        assert_eq!(
+            manager.restore_state(&path),
+            Err(IterManError::ListLengthChanged {
+                list: "subjects".to_string(),
+                saved: 2,
+                found: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_save_a_labeled_and_versioned_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("campaign.state");
+        let mut manager = Manager::new();
+        manager.combinations().take(8).for_each(drop);
+        manager.save_state(&path).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with(&format!("version {STATE_VERSION}\n")));
+        assert!(contents.contains("client_line_index 1\n"));
+        assert!(contents.contains("pair_index 2\n"));
+
+        std::fs::write(&path, contents.replace("version 1", "version 2")).unwrap();
+        assert!(matches!(
+            manager.restore_state(&path),
+            Err(IterManError::Io(e)) if e.contains("version 2")
+        ));
+
+        std::fs::write(&path, "1 0 2 0 2 0 3 41\n").unwrap();
+        assert!(matches!(
+            manager.restore_state(&path),
+            Err(IterManError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn it_should_yield_no_combinations_when_a_list_is_empty() {
+        let mut manager = Manager {
+            subjects: MemoryList::new(vec![]),
+            ..Manager::new()
+        };
+        assert_eq!(manager.combinations().count(), 0);
+        assert_eq!(manager.clients.line_index(), 0);
+    }
+
+    #[test]
+    fn it_should_zip_lists_in_lockstep() {
+        let mut manager = Manager::builder()
+            .clients(BufferList::new(BufReader::new(Cursor::new(
+                "a@b.com\nc@d.com\n",
+            ))))
+            .subjects(MemoryList::new(vec!["Hello", "Hi"]))
+            .landing_pages(MemoryList::new(vec!["https://a.com", "https://b.com"]))
+            .build()
+            .unwrap();
+        assert_eq!(
+            manager.zipped().collect::<Vec<_>>(),
+            [
+                (
+                    "a@b.com".to_string(),
+                    "Hello".to_string(),
+                    "https://a.com".to_string()
+                ),
+                (
+                    "c@d.com".to_string(),
+                    "Hi".to_string(),
+                    "https://b.com".to_string()
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_stop_zipping_at_the_shortest_list() {
+        let mut manager = Manager::new();
+        let zipped = manager.zipped().collect::<Vec<_>>();
+        assert_eq!(zipped.len(), 2);
+        assert_eq!(
+            zipped[1],
+            (
+                "test@web.com".to_string(),
+                "Since we last spoke".to_string(),
+                "https://business.com/lp/current".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_build_a_manager_from_supplied_lists() {
+        let mut manager = Manager::builder()
+            .clients(BufferList::new(BufReader::new(Cursor::new(
+                "a@b.com\nc@d.com\n",
+            ))))
+            .subjects(MemoryList::new(vec!["Hello".to_string(), "Hi".to_string()]))
+            .landing_pages(MemoryList::new(vec!["https://a.com".to_string()]))
+            .build()
+            .unwrap();
+
+        assert_eq!(manager.clients_mut().next().unwrap(), "a@b.com");
+        assert_eq!(manager.clients().line_index(), 1);
+        assert_eq!(manager.subjects_mut().next().unwrap(), "Hello");
+        assert_eq!(manager.landing_pages().remaining(), 1);
+        assert_eq!(manager.combinations().count(), 1);
+    }
+
+    #[test]
+    fn it_should_add_and_remove_named_lists() {
+        let mut manager = Manager::builder()
+            .clients(BufferList::new(BufReader::new(Cursor::new("a@b.com\n"))))
+            .subjects(MemoryList::new(vec!["Hello"]))
+            .landing_pages(MemoryList::new(vec!["https://a.com"]))
+            .list(
+                "senders",
+                MemoryList::new(vec!["Ann".to_string(), "Bo".to_string()]),
+            )
+            .build()
+            .unwrap();
+        manager.add_list(
+            "footers",
+            BufferList::new(BufReader::new(Cursor::new("Cheers\nBye\nLater\n"))),
+        );
+
+        let mut names = manager.list_names().collect::<Vec<&str>>();
+        names.sort();
+        assert_eq!(names, ["footers", "senders"]);
+
+        assert_eq!(manager.next_of("senders"), Some("Ann".to_string()));
+        assert_eq!(manager.next_of("missing"), None);
+        assert_eq!(
+            manager
+                .zipped_of(&["footers", "senders"])
+                .unwrap()
+                .collect::<Vec<_>>(),
+            [vec!["Cheers", "Bo"]]
+        );
+        assert_eq!(
+            manager.zipped_of(&["footers", "missing"]).err(),
+            Some(IterManError::MissingList("missing".to_string()))
+        );
+
+        assert!(manager.remove_list("senders").is_some());
+        assert_eq!(manager.next_of("senders"), None);
+        assert_eq!(
+            manager.combinations_of(&["missing"]).err(),
+            Some(IterManError::MissingList("missing".to_string()))
+        );
+        assert_eq!(manager.next_of("footers"), Some("Later".to_string()));
+    }
+
+    #[test]
+    fn it_should_combine_named_lists_with_the_first_outermost() {
+        let mut manager = Manager::new();
+        manager.add_list(
+            "senders",
+            BufferList::new(BufReader::new(Cursor::new("Ann\nBo\n"))),
+        );
+        manager.add_list(
+            "greetings",
+            MemoryList::new(vec!["Hi".to_string(), "Hey".to_string()]),
+        );
+        manager.add_list("footers", MemoryList::new(vec!["Cheers".to_string()]));
+        manager.add_list("empty", MemoryList::new(Vec::<String>::new()));
+
+        let rows = manager
+            .combinations_of(&["senders", "greetings", "footers"])
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            [
+                ["Ann", "Hi", "Cheers"],
+                ["Ann", "Hey", "Cheers"],
+                ["Bo", "Hi", "Cheers"],
+                ["Bo", "Hey", "Cheers"],
+            ]
+        );
+
+        manager.add_list("senders", MemoryList::new(vec!["Cy".to_string()]));
+        assert_eq!(
+            manager
+                .combinations_of(&["senders", "empty"])
+                .unwrap()
+                .count(),
+            0
+        );
+        assert_eq!(manager.next_of("senders"), Some("Cy".to_string()));
+        assert_eq!(manager.combinations_of(&[]).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn it_should_fail_to_build_without_every_list() {
+        let result = Manager::<Cursor<&str>, &str>::builder()
+            .subjects(MemoryList::new(vec!["Hello"]))
+            .build();
+        assert_eq!(
+            result.err(),
+            Some(IterManError::MissingList("clients".to_string()))
+        );
+    }
 }
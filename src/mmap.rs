@@ -0,0 +1,240 @@
+use crate::error::IterManError;
+use crate::list::ListLike;
+use memmap2::Mmap;
+use std::fs::File;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A [MmapList] is a [ListLike] over a memory-mapped file for large files that are seeked around
+/// a lot. The start of every line is recorded when the file is opened, so seeking by byte offset
+/// or by line is O(1) instead of re-reading through a [std::io::BufReader].
+///
+/// The file must not be changed by this or any other process while it's mapped.
+/// # Examples
+/// ```no-run
+/// let mut list = MmapList::from_path("clients.txt").unwrap();
+/// list.seek_to_line(1_000_000).unwrap();
+/// assert_eq!(list.line_index(), 1_000_000);
+/// ```
+pub struct MmapList {
+    mmap: Mmap,
+    round_robin: bool,
+    line_index: AtomicUsize,
+    bytes_offset: AtomicUsize,
+    /// The byte offset each line starts at.
+    line_offsets: Vec<usize>,
+}
+
+impl MmapList {
+    /// Creates a new [MmapList] over the file at `path`.
+    /// # Errors
+    /// Returns [IterManError::Io] if the file can't be opened or mapped.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, IterManError> {
+        let file = File::open(path)?;
+        // SAFETY: the caller guarantees the file isn't modified while it's mapped, see the
+        // type level docs.
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        let line_offsets = std::iter::once(0)
+            .chain(
+                mmap.iter()
+                    .enumerate()
+                    .filter(|(_, &b)| b == b'\n')
+                    .map(|(i, _)| i + 1),
+            )
+            .filter(|&offset| offset < mmap.len())
+            .collect();
+
+        Ok(Self {
+            mmap,
+            round_robin: false,
+            line_index: AtomicUsize::new(0),
+            bytes_offset: AtomicUsize::new(0),
+            line_offsets,
+        })
+    }
+
+    /// Creates a new [MmapList] over the file at `path` with `round_robin` turned on.
+    /// # Errors
+    /// Returns [IterManError::Io] if the file can't be opened or mapped.
+    pub fn from_path_round_robin<P: AsRef<Path>>(path: P) -> Result<Self, IterManError> {
+        Ok(Self {
+            round_robin: true,
+            ..Self::from_path(path)?
+        })
+    }
+
+    pub fn line_index(&self) -> usize {
+        self.line_index.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes_offset(&self) -> usize {
+        self.bytes_offset.load(Ordering::Relaxed)
+    }
+
+    /// The number of lines in the file.
+    pub fn total_len(&self) -> usize {
+        self.line_offsets.len()
+    }
+
+    /// Reset the line index and byte offset
+    pub fn reset(&mut self) {
+        self.line_index.store(0, Ordering::Relaxed);
+        self.bytes_offset.store(0, Ordering::Relaxed);
+    }
+
+    /// Turn `round_robin` on or off without reopening the file. Turning it on for a list that
+    /// has been read to the end makes the next read start again from the first line.
+    pub fn set_round_robin(&mut self, round_robin: bool) {
+        self.round_robin = round_robin;
+    }
+
+    /// Move to `bytes_offset` and set the `line_index` to match it.
+    /// # Errors
+    /// Returns [IterManError::StreamOutOfBounds] if `bytes_offset` is past the end of the file.
+    pub fn seek(&mut self, line_index: usize, bytes_offset: usize) -> Result<usize, IterManError> {
+        if bytes_offset > self.mmap.len() {
+            return Err(IterManError::StreamOutOfBounds {
+                line_index,
+                bytes_offset,
+                max_len: self.mmap.len(),
+            });
+        }
+
+        self.line_index.store(line_index, Ordering::Relaxed);
+        self.bytes_offset.store(bytes_offset, Ordering::Relaxed);
+        Ok(bytes_offset)
+    }
+
+    /// Seek to the start of line `line_index`, returning the resulting `bytes_offset`.
+    /// # Errors
+    /// Returns [IterManError::StreamOutOfBounds] if the file has fewer lines than `line_index`.
+    pub fn seek_to_line(&mut self, line_index: usize) -> Result<usize, IterManError> {
+        let bytes_offset = match line_index.cmp(&self.line_offsets.len()) {
+            std::cmp::Ordering::Less => self.line_offsets[line_index],
+            std::cmp::Ordering::Equal => self.mmap.len(),
+            std::cmp::Ordering::Greater => {
+                return Err(IterManError::StreamOutOfBounds {
+                    line_index,
+                    bytes_offset: self.mmap.len(),
+                    max_len: self.mmap.len(),
+                })
+            }
+        };
+        self.seek(line_index, bytes_offset)
+    }
+
+    /// The line starting at `bytes_offset` along with the bytes it takes up, wrapping to the
+    /// start when `round_robin` is on.
+    fn line_at(&self, bytes_offset: usize) -> Option<(Result<&str, IterManError>, usize, bool)> {
+        let (start, wrapped) = if bytes_offset < self.mmap.len() {
+            (bytes_offset, false)
+        } else if self.round_robin && !self.mmap.is_empty() {
+            (0, true)
+        } else {
+            return None;
+        };
+
+        let rest = &self.mmap[start..];
+        let len = rest
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(rest.len(), |i| i + 1);
+        let line = std::str::from_utf8(&rest[..len])
+            .map(str::trim)
+            .map_err(|e| IterManError::Utf8(e.to_string()));
+        Some((line, len, wrapped))
+    }
+}
+
+impl ListLike for MmapList {
+    type Item = String;
+
+    fn iter(&mut self) -> Option<Self::Item> {
+        self.try_next().ok().flatten()
+    }
+
+    /// Reads the next line, returning [IterManError::Utf8] for a line that isn't valid UTF-8,
+    /// where `iter` would return [None]. The invalid line is still skipped over.
+    fn try_next(&mut self) -> Result<Option<Self::Item>, IterManError> {
+        let Some((line, len, wrapped)) = self.line_at(self.bytes_offset()) else {
+            return Ok(None);
+        };
+        let line = line.map(str::to_string);
+
+        if wrapped {
+            self.reset();
+        }
+        self.line_index.fetch_add(1, Ordering::SeqCst);
+        self.bytes_offset.fetch_add(len, Ordering::SeqCst);
+        line.map(Some)
+    }
+
+    fn peek(&mut self) -> Option<Self::Item> {
+        let (line, _, _) = self.line_at(self.bytes_offset())?;
+        line.ok().map(str::to_string)
+    }
+}
+
+impl Iterator for MmapList {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::list::BufferList;
+
+    #[test]
+    fn mmap_list_should_match_buffer_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clients.txt");
+        std::fs::write(&path, "a@b.com\nc@d.com\n\ne@f.com").unwrap();
+
+        let lines = BufferList::from_path(&path)
+            .unwrap()
+            .collect::<Vec<String>>();
+        let list = MmapList::from_path(&path).unwrap();
+        assert_eq!(list.total_len(), 4);
+        assert_eq!(list.collect::<Vec<String>>(), lines);
+
+        let mut buffer_list = BufferList::from_path(&path).unwrap();
+        let mut list = MmapList::from_path(&path).unwrap();
+        for line_index in [3, 0, 2, 4] {
+            assert_eq!(
+                list.seek_to_line(line_index).unwrap(),
+                buffer_list.seek_to_line(line_index).unwrap()
+            );
+            assert_eq!(list.peek(), buffer_list.peek());
+            assert_eq!(list.next(), buffer_list.next());
+            assert_eq!(list.bytes_offset(), buffer_list.bytes_offset());
+        }
+        assert!(list.seek_to_line(5).is_err());
+
+        list.seek(1, 8).unwrap();
+        assert_eq!(list.next(), Some("c@d.com".to_string()));
+        assert!(list.seek(0, 100).is_err());
+    }
+
+    #[test]
+    fn mmap_list_should_wrap_with_round_robin() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clients.txt");
+        std::fs::write(&path, "1\n2\n").unwrap();
+
+        let mut list = MmapList::from_path_round_robin(&path).unwrap();
+        assert_eq!(
+            list.by_ref().take(5).collect::<Vec<String>>(),
+            ["1", "2", "1", "2", "1"]
+        );
+        assert_eq!(list.line_index(), 1);
+
+        std::fs::write(&path, "").unwrap();
+        let mut list = MmapList::from_path_round_robin(&path).unwrap();
+        assert_eq!(list.next(), None);
+    }
+}
@@ -0,0 +1,57 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// A seeded permutation of list indices, used to walk a list in a random but reproducible order.
+#[derive(Clone)]
+pub(crate) struct Shuffle {
+    order: Vec<usize>,
+    rng: StdRng,
+}
+
+impl Shuffle {
+    pub(crate) fn new(len: usize, seed: u64) -> Self {
+        let mut shuffle = Self {
+            order: (0..len).collect(),
+            rng: StdRng::seed_from_u64(seed),
+        };
+        shuffle.reshuffle();
+        shuffle
+    }
+
+    /// Draw a new permutation from the same seeded generator.
+    pub(crate) fn reshuffle(&mut self) {
+        self.order.shuffle(&mut self.rng);
+    }
+
+    /// Append indices for items added to the end of the list. They're read in insertion order
+    /// after the current order until the next reshuffle.
+    pub(crate) fn grow(&mut self, len: usize) {
+        let start = self.order.len();
+        self.order.extend(start..len);
+    }
+
+    /// Map a position in the shuffled order to an index in the underlying list.
+    pub(crate) fn index(&self, line_index: usize) -> usize {
+        self.order.get(line_index).copied().unwrap_or(line_index)
+    }
+}
+
+/// A seeded generator that picks one of several candidates at a time.
+#[derive(Clone)]
+pub(crate) struct Picker {
+    rng: StdRng,
+}
+
+impl Picker {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Pick one of `candidates`, or [None] if there are none.
+    pub(crate) fn pick(&mut self, candidates: &[usize]) -> Option<usize> {
+        candidates.choose(&mut self.rng).copied()
+    }
+}
@@ -0,0 +1,313 @@
+use std::io::{Read, Seek, SeekFrom};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncSeek, AsyncSeekExt, BufReader};
+use tokio::net::TcpStream;
+
+use crate::cursor::Position;
+use crate::error::IterManError;
+use crate::list::{BufferList, ListLike};
+
+/// An async counterpart to [crate::BufferList] that reads lines from a [tokio::io::BufReader],
+/// tracking the same `line_index` and `bytes_offset` so it can be saved and resumed.
+/// # Examples
+/// ```no-run
+/// let reader = BufReader::new(Cursor::new("hello\nworld"));
+/// let mut list = StreamList::new(reader);
+/// assert_eq!(list.iter().await, Some("hello".to_string()));
+/// ```
+pub struct StreamList<R: AsyncRead + Unpin> {
+    buf_reader: BufReader<R>,
+    round_robin: bool,
+    line_index: AtomicUsize,
+    bytes_offset: AtomicUsize,
+    /// How to move the reader back to the start for `round_robin`. [None] for a forward-only
+    /// reader, which can't turn `round_robin` on.
+    rewind: Option<Rewind<R>>,
+}
+
+/// The [AsyncSeek] methods of a seekable reader, kept so `iter` can rewind it without requiring
+/// every reader to be seekable.
+struct Rewind<R> {
+    start: fn(Pin<&mut BufReader<R>>, SeekFrom) -> std::io::Result<()>,
+    complete: PollComplete<R>,
+}
+
+type PollComplete<R> = fn(Pin<&mut BufReader<R>>, &mut Context<'_>) -> Poll<std::io::Result<u64>>;
+
+impl<R: AsyncRead + AsyncSeek + Unpin> StreamList<R> {
+    pub fn new(buf_reader: BufReader<R>) -> Self {
+        Self {
+            rewind: Some(Rewind {
+                start: AsyncSeek::start_seek,
+                complete: AsyncSeek::poll_complete,
+            }),
+            ..Self::forward(buf_reader)
+        }
+    }
+
+    /// Creates a new [StreamList] with `round_robin` turned on.
+    pub fn new_round_robin(buf_reader: BufReader<R>) -> Self {
+        Self {
+            round_robin: true,
+            ..Self::new(buf_reader)
+        }
+    }
+
+    /// Move the reader to `bytes_offset` and set the `line_index` to match it.
+    /// # Errors
+    /// Returns [IterManError::StreamOutOfBounds] if `bytes_offset` is past the end of the stream.
+    pub async fn seek(
+        &mut self,
+        line_index: usize,
+        bytes_offset: usize,
+    ) -> Result<usize, IterManError> {
+        let out_of_bounds = |max_len| IterManError::StreamOutOfBounds {
+            line_index,
+            bytes_offset,
+            max_len,
+        };
+
+        let stream_len = self
+            .buf_reader
+            .seek(SeekFrom::End(0))
+            .await
+            .map_err(|_| out_of_bounds(0))?;
+
+        if stream_len < bytes_offset as u64 {
+            return Err(out_of_bounds(stream_len as usize));
+        }
+
+        self.buf_reader
+            .seek(SeekFrom::Start(bytes_offset as u64))
+            .await
+            .map_err(|_| out_of_bounds(stream_len as usize))?;
+
+        self.line_index.store(line_index, Ordering::Relaxed);
+        self.bytes_offset.store(bytes_offset, Ordering::Relaxed);
+        Ok(self.bytes_offset())
+    }
+}
+
+impl StreamList<TcpStream> {
+    /// Creates a forward-only [StreamList] over lines arriving on a TCP connection. Each `iter`
+    /// waits for the next full line, and nothing is read ahead beyond the buffer, so a slow
+    /// consumer leaves data in the socket and the sender is held back by TCP flow control. A live
+    /// socket can't be rewound, so `seek` isn't available, `set_round_robin(true)` is rejected,
+    /// and `iter` returns [None] once the peer closes the connection.
+    /// # Examples
+    /// ```no-run
+    /// let stream = TcpStream::connect("127.0.0.1:7000").await?;
+    /// let mut list = StreamList::from_tcp(stream);
+    /// while let Some(line) = list.iter().await {
+    ///     println!("{line}");
+    /// }
+    /// ```
+    pub fn from_tcp(stream: TcpStream) -> Self {
+        Self::forward(BufReader::new(stream))
+    }
+}
+
+impl<R: AsyncRead + Unpin> StreamList<R> {
+    /// A [StreamList] that can't rewind, so `round_robin` stays off.
+    fn forward(buf_reader: BufReader<R>) -> Self {
+        Self {
+            buf_reader,
+            round_robin: false,
+            line_index: AtomicUsize::new(0),
+            bytes_offset: AtomicUsize::new(0),
+            rewind: None,
+        }
+    }
+
+    /// Turn `round_robin` on or off without rebuilding the list. Turning it on for a stream
+    /// that has been read to the end makes the next read rewind to the first line.
+    /// # Errors
+    /// Returns [IterManError::NotSeekable] when turning it on for a forward-only stream, such as
+    /// one from [StreamList::from_tcp].
+    pub fn set_round_robin(&mut self, round_robin: bool) -> Result<(), IterManError> {
+        if round_robin && self.rewind.is_none() {
+            return Err(IterManError::NotSeekable);
+        }
+        self.round_robin = round_robin;
+        Ok(())
+    }
+
+    /// Used internally to manage the line index and byte offset
+    fn incr(&mut self, bytes_read: &usize) {
+        self.line_index.fetch_add(1, Ordering::SeqCst);
+        self.bytes_offset.fetch_add(*bytes_read, Ordering::SeqCst);
+    }
+
+    /// Reset the line index and byte offset once `iter` has rewound the reader.
+    fn reset(&mut self) {
+        self.line_index.store(0, Ordering::Relaxed);
+        self.bytes_offset.store(0, Ordering::Relaxed);
+    }
+
+    pub fn line_index(&self) -> usize {
+        self.line_index.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes_offset(&self) -> usize {
+        self.bytes_offset.load(Ordering::Relaxed)
+    }
+
+    /// The current `line_index` and `bytes_offset` as a single [Position].
+    pub fn position(&self) -> Position {
+        Position {
+            line_index: self.line_index(),
+            bytes_offset: Some(self.bytes_offset()),
+        }
+    }
+
+    /// Read the next line, trimmed, or `None` once the stream is exhausted. With `round_robin` the
+    /// stream is rewound to the start instead.
+    pub async fn iter(&mut self) -> Option<String> {
+        let mut line = String::new();
+        let mut bytes_read = self.buf_reader.read_line(&mut line).await.ok()?;
+
+        if bytes_read == 0 {
+            let rewind = self.rewind.as_ref().filter(|_| self.round_robin)?;
+            let (start, complete) = (rewind.start, rewind.complete);
+
+            start(Pin::new(&mut self.buf_reader), SeekFrom::Start(0)).ok()?;
+            std::future::poll_fn(|cx| complete(Pin::new(&mut self.buf_reader), cx))
+                .await
+                .ok()?;
+            self.reset();
+
+            bytes_read = self.buf_reader.read_line(&mut line).await.ok()?;
+            if bytes_read == 0 {
+                return None; // Needed to stop empty buffer from returning ""
+            }
+        }
+
+        self.incr(&bytes_read);
+        Some(line.trim().to_string())
+    }
+}
+
+/// A [futures_core::Stream] over a [BufferList], created by [BufferList::into_stream], with the
+/// same `round_robin` and `seek` behavior as iterating it. The underlying reader is blocking, so
+/// each poll reads the next line on the calling thread and is always ready. That suits files and
+/// in-memory readers, where a read is quick, but it gives no backpressure and stalls the executor
+/// on slow readers. Use [StreamList] for readers that need to be awaited.
+pub struct BufferStream<T: Read + Seek> {
+    list: BufferList<T>,
+}
+
+impl<T: Read + Seek> BufferStream<T> {
+    pub(crate) fn new(list: BufferList<T>) -> Self {
+        Self { list }
+    }
+
+    /// The underlying list, for reading its position.
+    pub fn get_ref(&self) -> &BufferList<T> {
+        &self.list
+    }
+
+    /// Unwrap the underlying list.
+    pub fn into_inner(self) -> BufferList<T> {
+        self.list
+    }
+}
+
+impl<T: Read + Seek> futures_core::Stream for BufferStream<T> {
+    type Item = String;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.get_mut().list.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    fn mock_stream_reader() -> BufReader<Cursor<&'static str>> {
+        BufReader::new(Cursor::new("1\n2\n3\n"))
+    }
+
+    #[tokio::test]
+    async fn stream_list_should_read_lines() {
+        let mut list = StreamList::new(mock_stream_reader());
+        assert_eq!(list.iter().await, Some("1".to_string()));
+        assert_eq!(list.iter().await, Some("2".to_string()));
+        assert_eq!(list.iter().await, Some("3".to_string()));
+        assert_eq!(list.iter().await, None);
+        assert_eq!(list.line_index(), 3);
+        assert_eq!(list.bytes_offset(), 6);
+        assert_eq!(
+            list.position(),
+            Position {
+                line_index: 3,
+                bytes_offset: Some(6),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_list_should_round_robin() {
+        let mut list = StreamList::new_round_robin(mock_stream_reader());
+        for _ in 0..3 {
+            list.iter().await;
+        }
+        assert_eq!(list.iter().await, Some("1".to_string()));
+        assert_eq!(list.line_index(), 1);
+        assert_eq!(list.bytes_offset(), 2);
+
+        let mut list = StreamList::new(mock_stream_reader());
+        assert_eq!(list.set_round_robin(true), Ok(()));
+        for _ in 0..3 {
+            list.iter().await;
+        }
+        assert_eq!(list.iter().await, Some("1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn stream_list_should_read_lines_from_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket.write_all(b"1\n2\n").await.unwrap();
+            socket.write_all(b"3\n").await.unwrap();
+        });
+
+        let mut list = StreamList::from_tcp(TcpStream::connect(addr).await.unwrap());
+        assert_eq!(list.set_round_robin(true), Err(IterManError::NotSeekable));
+        assert_eq!(list.set_round_robin(false), Ok(()));
+        assert_eq!(list.iter().await, Some("1".to_string()));
+        assert_eq!(list.iter().await, Some("2".to_string()));
+        assert_eq!(list.iter().await, Some("3".to_string()));
+        assert_eq!(list.iter().await, None);
+        assert_eq!(list.line_index(), 3);
+        assert_eq!(list.bytes_offset(), 6);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn stream_list_should_seek() {
+        let mut list = StreamList::new(mock_stream_reader());
+        assert_eq!(list.seek(2, 4).await, Ok(4));
+        assert_eq!(list.iter().await, Some("3".to_string()));
+        assert_eq!(list.line_index(), 3);
+
+        assert_eq!(
+            list.seek(9, 20).await,
+            Err(IterManError::StreamOutOfBounds {
+                line_index: 9,
+                bytes_offset: 20,
+                max_len: 6,
+            })
+        );
+    }
+}